@@ -18,9 +18,14 @@ fn main() {
 
     nn.set_weight(1, 0, 0, 0.99);
 
-    dump_model(&nn, "model.bin").expect("Failed to dump model");
+    // Written to the temp directory: the `model.bin` in the repository is kept as a
+    // sample of the version 1 format, from before model files had a header.
+    let path = std::env::temp_dir().join("only-brain-dump-load.bin");
+    let path = path.to_str().expect("temp dir should be valid UTF-8");
 
-    let nn2 = load_model("model.bin").expect("Failed to load model");
+    dump_model(&nn, path).expect("Failed to dump model");
+
+    let nn2 = load_model(path).expect("Failed to load model");
 
     nn2.print();
 }
@@ -0,0 +1,97 @@
+use polars::lazy::dsl::{col, lit};
+use polars::prelude::*;
+
+use only_brain::{
+    Adaline, BVector, PocketPerceptron, Scaler, StandardScaler, TrainOptions, VotedPerceptron,
+};
+
+fn accuracy(
+    predict: impl Fn(&BVector<f64, 2>) -> f64,
+    samples: &[BVector<f64, 2>],
+    labels: &[f64],
+) -> f64 {
+    let correct = samples
+        .iter()
+        .zip(labels)
+        .filter(|(sample, &label)| predict(sample) == label)
+        .count();
+    correct as f64 / samples.len() as f64
+}
+
+fn main() -> PolarsResult<()> {
+    // The same Iris subset as the polars example: setosa (0) against versicolor (1),
+    // using the sepal measurements only.
+    let df = CsvReadOptions::default()
+        .with_has_header(true)
+        .try_into_reader_with_file_path(Some("examples/datasets/iris.csv".into()))?
+        .finish()?
+        .lazy()
+        .filter(
+            col("Species")
+                .eq(lit("Iris-setosa"))
+                .or(col("Species").eq(lit("Iris-versicolor"))),
+        )
+        .collect()?;
+
+    let sepal_length = df.column("SepalLengthCm")?.f64()?;
+    let sepal_width = df.column("SepalWidthCm")?.f64()?;
+    let rows: Vec<Vec<f64>> = sepal_length
+        .into_no_null_iter()
+        .zip(sepal_width.into_no_null_iter())
+        .map(|(sl, sw)| vec![sl, sw])
+        .collect();
+    let labels: Vec<f64> = df
+        .column("Species")?
+        .str()?
+        .into_no_null_iter()
+        .map(|name| if name == "Iris-setosa" { 0.0 } else { 1.0 })
+        .collect();
+
+    // Adaline needs scaled features, so every learner gets the same scaled inputs.
+    let scaler = Scaler::from(StandardScaler::fit_rows(&rows));
+    let samples: Vec<BVector<f64, 2>> = rows
+        .iter()
+        .map(|row| {
+            let scaled = scaler.transform(row);
+            BVector::from_array([scaled[0], scaled[1]])
+        })
+        .collect();
+
+    let options = TrainOptions {
+        eta: 0.01,
+        epochs: 50,
+        shuffle_seed: Some(7),
+        stop_on_convergence: false,
+    };
+
+    let mut adaline = Adaline::<2>::new();
+    adaline.train_with(&samples, &labels, &options);
+    print!("{}", adaline);
+    println!(
+        "Accuracy: {:.3}\n",
+        accuracy(|x| adaline.predict(x), &samples, &labels)
+    );
+
+    let mut pocket = PocketPerceptron::<2>::new();
+    pocket.train_with(&samples, &labels, &options);
+    print!("{}", pocket);
+    println!(
+        "Accuracy: {:.3}\n",
+        accuracy(|x| pocket.feed_forward(x), &samples, &labels)
+    );
+
+    let mut voted = VotedPerceptron::<2>::new();
+    voted.train_with(&samples, &labels, &options);
+    let averaged = voted.averaged();
+    print!("{}", voted);
+    println!(
+        "Voted Accuracy: {:.3}",
+        accuracy(|x| voted.feed_forward(x), &samples, &labels)
+    );
+    println!(
+        "Averaged Accuracy: {:.3}",
+        accuracy(|x| averaged.feed_forward(x), &samples, &labels)
+    );
+
+    Ok(())
+}
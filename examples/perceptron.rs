@@ -1,7 +1,7 @@
 use only_brain::{bvector, ActivationFunction::BinaryStep, Perceptron};
 
 fn main() {
-    let mut perceptron: Perceptron<2> = Perceptron::new(BinaryStep);
+    let mut perceptron: Perceptron<f64, 2> = Perceptron::new(BinaryStep);
 
     let weights = bvector![1.0, 1.0];
     perceptron.set_weights(weights);
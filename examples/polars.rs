@@ -1,12 +1,10 @@
-
-use polars::prelude::*;
-use polars::lazy::dsl::{col, lit};
 use itertools::izip;
+use polars::lazy::dsl::{col, lit};
+use polars::prelude::*;
 
-use only_brain::{Perceptron, ActivationFunction, bvector};
+use only_brain::{bvector, ActivationFunction, BVector, Perceptron, TrainOptions};
 
 fn main() -> PolarsResult<()> {
-
     // 1. Load the Iris dataset and simplify the problem to binary classification
     //    so that a perceptron can be better used.
 
@@ -30,9 +28,9 @@ fn main() -> PolarsResult<()> {
 
     // Map species strings to f64 (0.0 / 1.0)
     let species_f64 = df
-        .column("Species")?        // &Series
-        .str()?                    // &StringChunked
-        .into_iter()               // Iterator<Option<&str>>
+        .column("Species")? // &Series
+        .str()? // &StringChunked
+        .into_iter() // Iterator<Option<&str>>
         .map(|opt_name| match opt_name {
             Some("Iris-setosa") => Some(0.0),
             Some("Iris-versicolor") => Some(1.0),
@@ -52,36 +50,32 @@ fn main() -> PolarsResult<()> {
     let sepal_width = df.column("SepalWidthCm")?.f64()?;
     let species = df.column("Species")?.f64()?;
 
-    let eta = 0.1;
-
-    let mut perceptron = Perceptron::<2>::new(ActivationFunction::BinaryStep);
-
-    let mut weights = bvector![1.0, 1.0];
-    let mut bias = 0.0;
-
-    perceptron.set_weights(weights.clone());
-    perceptron.set_bias(bias);
-
-    let vectors = izip!(
+    let samples: Vec<BVector<f64, 2>> = izip!(
         sepal_length.into_no_null_iter(),
-        sepal_width.into_no_null_iter(),
-        species.into_no_null_iter()
+        sepal_width.into_no_null_iter()
+    )
+    .map(|(sl, sw)| bvector![sl, sw])
+    .collect();
+    let labels: Vec<f64> = species.into_no_null_iter().collect();
+
+    let mut perceptron = Perceptron::<f64, 2>::new(ActivationFunction::BinaryStep);
+
+    // w += eta * (target - output) * x, visiting the samples in a new order every epoch
+    let history = perceptron.train_with(
+        &samples,
+        &labels,
+        &TrainOptions {
+            eta: 0.1,
+            epochs: 100,
+            shuffle_seed: Some(42),
+            ..TrainOptions::default()
+        },
     );
 
-    // gradient descent learning
-    for (sl, sw, sp) in vectors {
-        let output = perceptron.feed_forward(&bvector![sl, sw]);
-        if output != sp {
-            weights = bvector![
-                weights.get(0) + eta * sp * sl,
-                weights.get(1) + eta * sp * sw,
-            ];
-            bias = bias + eta * sp;
-            perceptron.set_weights(weights.clone());
-            perceptron.set_bias(bias);
-        }
-        println!("Sepal length: {sl}, Sepal width: {sw}, Species: {sp}, Output: {output}");
+    for (epoch, misclassified) in history.misclassifications.iter().enumerate() {
+        println!("Epoch {}: {} misclassified", epoch + 1, misclassified);
     }
+    println!("Converged: {}", history.converged);
 
     println!("\nFinal Perceptron: {}", perceptron);
 
@@ -0,0 +1,96 @@
+#ifndef ONLY_BRAIN_H
+#define ONLY_BRAIN_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Status code returned by the fallible functions of the C API.
+typedef enum ObStatus {
+  OB_STATUS_OK = 0,
+  OB_STATUS_NULL_POINTER = 1,
+  OB_STATUS_INVALID_PATH = 2,
+  OB_STATUS_IO = 3,
+  OB_STATUS_INVALID_MODEL = 4,
+  OB_STATUS_SIZE_MISMATCH = 5,
+  OB_STATUS_PANIC = 6,
+} ObStatus;
+
+// Opaque handle to a loaded model. Create it with [`ob_load_model`] or
+// [`ob_load_model_from_buffer`] and release it with [`ob_free_model`].
+typedef struct ObModel ObModel;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Returns a description of the last error on the calling thread, or null if there was
+// none. The string stays valid until the next failing call on the same thread.
+const char *ob_last_error_message(void);
+
+// Loads a model written by `dump_model` from the file at `path`.
+//
+// # Safety
+//
+// `path` must be a valid NUL-terminated string and `out_model` must point to writable
+// memory for one pointer.
+enum ObStatus ob_load_model(const char *path, struct ObModel **out_model);
+
+// Loads a model from the `len` bytes at `data`, in the format written by `dump_model`.
+//
+// # Safety
+//
+// `data` must point to `len` readable bytes and `out_model` must point to writable
+// memory for one pointer.
+enum ObStatus ob_load_model_from_buffer(const uint8_t *data,
+                                        size_t len,
+                                        struct ObModel **out_model);
+
+// Returns the number of layers of the model, including the input layer, or 0 if
+// `model` is null or the call panicked.
+//
+// # Safety
+//
+// `model` must be null or a pointer returned by one of the load functions that has not
+// been freed.
+size_t ob_num_layers(const struct ObModel *model);
+
+// Returns the number of neurons of the given layer, or 0 if `model` is null, the
+// model has no layers, the layer does not exist or the call panicked. Layer 0 is the
+// input layer. Except for a null `model`, the reason is recorded as the last error.
+//
+// # Safety
+//
+// Same as [`ob_num_layers`].
+size_t ob_layer_size(const struct ObModel *model, size_t layer);
+
+// Feeds `num_inputs` values to the model and writes the outputs to `outputs`.
+// `num_inputs` must equal the input layer size and `num_outputs` the output layer size.
+//
+// # Safety
+//
+// `model` must be a live model handle, `inputs` must point to `num_inputs` readable
+// doubles and `outputs` to `num_outputs` writable doubles.
+enum ObStatus ob_feed_forward(const struct ObModel *model,
+                              const double *inputs,
+                              size_t num_inputs,
+                              double *outputs,
+                              size_t num_outputs);
+
+// Releases a model. Passing null is a no-op.
+//
+// # Safety
+//
+// `model` must be null or a pointer returned by one of the load functions, and must not
+// be used afterwards.
+void ob_free_model(struct ObModel *model);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ONLY_BRAIN_H */
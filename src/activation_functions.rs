@@ -1,3 +1,4 @@
+use nalgebra::RealField;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Serialize, Deserialize)]
@@ -9,23 +10,29 @@ pub enum ActivationFunction {
     BinaryStep,
 }
 
-pub fn sigmoid(x: f64) -> f64 {
-    1. / (1. + (-x).exp())
+// Written against `RealField` so `f32` perceptrons work too. nalgebra's scalar traits
+// fall back to `libm` for `exp` and `tanh` in `no_std` builds.
+pub fn sigmoid<T: RealField>(x: T) -> T {
+    T::one() / (T::one() + (-x).exp())
 }
 
-pub fn tanh(x: f64) -> f64 {
+pub fn tanh<T: RealField>(x: T) -> T {
     x.tanh()
 }
 
-pub fn relu(x: f64) -> f64 {
-    x.max(0.0)
+pub fn relu<T: RealField>(x: T) -> T {
+    x.max(T::zero())
 }
 
-pub fn binary_step(x: f64) -> f64 {
-    if x >= 0.0 { 1.0 } else { 0.0 }
+pub fn binary_step<T: RealField>(x: T) -> T {
+    if x >= T::zero() {
+        T::one()
+    } else {
+        T::zero()
+    }
 }
 
-pub fn get_activation_function(func: ActivationFunction) -> fn(f64) -> f64 {
+pub fn get_activation_function<T: RealField>(func: ActivationFunction) -> fn(T) -> T {
     match func {
         ActivationFunction::Sigmoid => sigmoid,
         ActivationFunction::Tanh => tanh,
@@ -93,9 +100,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn single_precision_matches_double_precision() {
+        for variant in [
+            ActivationFunction::Sigmoid,
+            ActivationFunction::Tanh,
+            ActivationFunction::ReLU,
+            ActivationFunction::BinaryStep,
+        ] {
+            for x in [-2.0f32, -0.5, 0.0, 0.5, 2.0] {
+                let single = get_activation_function::<f32>(variant)(x);
+                let double = get_activation_function::<f64>(variant)(x as f64);
+                assert!(
+                    (single as f64 - double).abs() < 1e-6,
+                    "{variant:?}({x}): {single} vs {double}"
+                );
+            }
+        }
+    }
+
     #[test]
     fn default_activation_function_is_sigmoid() {
         assert_eq!(ActivationFunction::default(), ActivationFunction::Sigmoid);
     }
 }
-
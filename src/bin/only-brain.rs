@@ -0,0 +1,252 @@
+//! `only-brain`: inspect, convert, compare and run dumped models from the command line.
+//!
+//! Build it with `cargo install only-brain --features cli`.
+
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use only_brain::{diff, dump_model, load_model, NeuralNetwork};
+
+type CliResult<T> = Result<T, Box<dyn Error>>;
+
+#[derive(Parser)]
+#[command(
+    name = "only-brain",
+    version,
+    about = "Inspect, convert and run only-brain models"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the layers, activation function and parameter counts of a model.
+    Info {
+        model: String,
+        #[arg(long, value_enum)]
+        format: Option<Format>,
+    },
+    /// Convert a model between the bincode and JSON formats.
+    Convert {
+        input: String,
+        output: String,
+        /// Format of the input. Guessed from the extension when omitted.
+        #[arg(long, value_enum)]
+        from: Option<Format>,
+        /// Format of the output. Guessed from the extension when omitted.
+        #[arg(long, value_enum)]
+        to: Option<Format>,
+    },
+    /// Run a model over CSV rows read from a file or stdin and write the outputs as CSV.
+    Predict {
+        model: String,
+        /// CSV file with one sample per row. Reads stdin when omitted.
+        #[arg(long, short)]
+        input: Option<String>,
+        #[arg(long, value_enum)]
+        format: Option<Format>,
+    },
+    /// Compare two models layer by layer.
+    Diff {
+        a: String,
+        b: String,
+        /// Exit with status 1 unless every weight and bias is within this distance.
+        #[arg(long)]
+        epsilon: Option<f64>,
+        /// Format of both models. Guessed from each extension when omitted.
+        #[arg(long, value_enum)]
+        format: Option<Format>,
+    },
+    /// Check that a model is internally consistent and has only finite parameters.
+    Validate {
+        model: String,
+        #[arg(long, value_enum)]
+        format: Option<Format>,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+enum Format {
+    /// The format written by `dump_model`.
+    Bincode,
+    Json,
+}
+
+impl Format {
+    fn resolve(explicit: Option<Format>, path: &str) -> Format {
+        explicit.unwrap_or_else(
+            || match Path::new(path).extension().and_then(|e| e.to_str()) {
+                Some("json") => Format::Json,
+                _ => Format::Bincode,
+            },
+        )
+    }
+}
+
+/// Loads a model and, like `load_model` does for bincode, checks that its layers fit
+/// together, so the commands can index and run it.
+fn load(path: &str, format: Option<Format>) -> CliResult<NeuralNetwork> {
+    match Format::resolve(format, path) {
+        Format::Bincode => load_model(path),
+        Format::Json => {
+            let model: NeuralNetwork = serde_json::from_reader(BufReader::new(File::open(path)?))?;
+            model.check_structure()?;
+            Ok(model)
+        }
+    }
+}
+
+fn dump(model: &NeuralNetwork, path: &str, format: Option<Format>) -> CliResult<()> {
+    match Format::resolve(format, path) {
+        Format::Bincode => dump_model(model, path),
+        Format::Json => {
+            let mut writer = BufWriter::new(File::create(path)?);
+            serde_json::to_writer_pretty(&mut writer, model)?;
+            writer.write_all(b"\n")?;
+            Ok(())
+        }
+    }
+}
+
+fn info(model: &NeuralNetwork) {
+    if let Some(metadata) = model.metadata() {
+        print!("{}", metadata);
+    }
+    println!("Activation Function: {:?}", model.activation_function());
+    println!();
+    print!("{}", model.summary());
+}
+
+/// Parses numeric CSV rows. A first line that is not numeric is treated as a header.
+fn read_rows(reader: impl BufRead) -> CliResult<Vec<Vec<f64>>> {
+    let mut rows = Vec::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let parsed: Result<Vec<f64>, _> = line
+            .split(',')
+            .map(|field| field.trim().parse::<f64>())
+            .collect();
+        match parsed {
+            Ok(row) => rows.push(row),
+            Err(_) if index == 0 => continue,
+            Err(error) => return Err(format!("line {}: {}", index + 1, error).into()),
+        }
+    }
+    Ok(rows)
+}
+
+fn predict(model: &NeuralNetwork, rows: &[Vec<f64>], out: impl Write) -> CliResult<()> {
+    let inputs = model.layer_size(0);
+    if let Some((index, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != inputs) {
+        return Err(format!(
+            "row {} has {} values, the model takes {}",
+            index + 1,
+            row.len(),
+            inputs
+        )
+        .into());
+    }
+
+    let mut out = BufWriter::new(out);
+    let outputs = model.layer_size(model.num_layers() - 1);
+    let header: Vec<String> = (0..outputs).map(|i| format!("output_{}", i)).collect();
+    writeln!(out, "{}", header.join(","))?;
+
+    for output in model.feed_forward_many(rows) {
+        let fields: Vec<String> = output.iter().map(f64::to_string).collect();
+        writeln!(out, "{}", fields.join(","))?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Returns every problem found in a loaded model; an empty list means it is valid. The
+/// layers already fit together, since loading checks that.
+fn validate(model: &NeuralNetwork) -> Vec<String> {
+    (1..model.num_layers())
+        .filter(|&layer| {
+            let stats = model.layer_stats(layer);
+            // NaN and infinities both propagate into the mean.
+            !stats.weights.mean.is_finite() || !stats.biases.mean.is_finite()
+        })
+        .map(|layer| format!("layer {} has non-finite weights or biases", layer))
+        .collect()
+}
+
+fn run(cli: Cli) -> CliResult<ExitCode> {
+    match cli.command {
+        Command::Info { model, format } => info(&load(&model, format)?),
+        Command::Convert {
+            input,
+            output,
+            from,
+            to,
+        } => {
+            let model = load(&input, from)?;
+            dump(&model, &output, to)?;
+        }
+        Command::Predict {
+            model,
+            input,
+            format,
+        } => {
+            let model = load(&model, format)?;
+            let rows = match input {
+                Some(path) => read_rows(BufReader::new(File::open(path)?))?,
+                None => read_rows(io::stdin().lock())?,
+            };
+            predict(&model, &rows, io::stdout().lock())?;
+        }
+        Command::Diff {
+            a,
+            b,
+            epsilon,
+            format,
+        } => {
+            let (a, b) = (load(&a, format)?, load(&b, format)?);
+            print!("{}", diff(&a, &b));
+            if let Some(epsilon) = epsilon {
+                if !a.approx_eq(&b, epsilon) {
+                    return Ok(ExitCode::FAILURE);
+                }
+            }
+        }
+        Command::Validate { model, format } => {
+            let problems = match load(&model, format) {
+                Ok(model) => validate(&model),
+                // Only a file that cannot be read is an error; one that does not decode
+                // into a consistent network is an invalid model.
+                Err(error) if !error.is::<io::Error>() => vec![error.to_string()],
+                Err(error) => return Err(error),
+            };
+            if !problems.is_empty() {
+                for problem in problems {
+                    eprintln!("{}", problem);
+                }
+                return Ok(ExitCode::FAILURE);
+            }
+            println!("ok");
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(code) => code,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::from(2)
+        }
+    }
+}
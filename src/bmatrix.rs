@@ -0,0 +1,194 @@
+use core::ops;
+
+use nalgebra::{SMatrix, SimdRealField};
+
+use crate::BVector;
+
+/// A matrix with `R` rows and `C` columns known at compile time, the companion of
+/// [`BVector`]. Multiplying it by a `BVector<T, C>` gives a `BVector<T, R>`, so shape
+/// mismatches are caught by the compiler.
+///
+/// # Example
+///
+/// ```
+/// use only_brain::{bmatrix, bvector};
+///
+/// let m = bmatrix![1.0, 2.0, 3.0;
+///                  4.0, 5.0, 6.0];
+///
+/// assert_eq!(m.clone() * bvector![1.0, 0.0, -1.0], bvector![-2.0, -2.0]);
+/// assert_eq!(m.transpose()[(2, 1)], 6.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct BMatrix<T: SimdRealField + Copy, const R: usize, const C: usize> {
+    pub data_matrix: SMatrix<T, R, C>,
+}
+
+impl<T: SimdRealField + Copy, const R: usize, const C: usize> BMatrix<T, R, C> {
+    /// Constructs a matrix filled with `element`.
+    pub fn from_element(element: T) -> Self {
+        BMatrix {
+            data_matrix: SMatrix::<T, R, C>::from_element(element),
+        }
+    }
+
+    /// Constructs the matrix by calling `f` with each `(row, column)` index.
+    pub fn from_fn(f: impl FnMut(usize, usize) -> T) -> Self {
+        BMatrix {
+            data_matrix: SMatrix::<T, R, C>::from_fn(f),
+        }
+    }
+
+    /// Constructs the matrix from an array of rows.
+    pub fn from_rows(rows: [[T; C]; R]) -> Self {
+        Self::from_fn(|row, col| rows[row][col])
+    }
+
+    pub fn nrows(&self) -> usize {
+        R
+    }
+
+    pub fn ncols(&self) -> usize {
+        C
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> T {
+        assert!(row < R && col < C, "Index out of bounds");
+        self.data_matrix[(row, col)]
+    }
+
+    pub fn transpose(&self) -> BMatrix<T, C, R> {
+        BMatrix {
+            data_matrix: self.data_matrix.transpose(),
+        }
+    }
+
+    /// Returns row `row` as a vector.
+    pub fn row(&self, row: usize) -> BVector<T, C> {
+        BVector::from_fn(|col| self.get(row, col))
+    }
+
+    /// Returns column `col` as a vector.
+    pub fn column(&self, col: usize) -> BVector<T, R> {
+        BVector::from_fn(|row| self.get(row, col))
+    }
+}
+
+impl<T: SimdRealField + Copy, const R: usize, const C: usize> ops::Mul<BVector<T, C>>
+    for BMatrix<T, R, C>
+{
+    type Output = BVector<T, R>;
+
+    fn mul(self, rhs: BVector<T, C>) -> Self::Output {
+        BVector {
+            data_vector: self.data_matrix * rhs.data_vector,
+        }
+    }
+}
+
+impl<T: SimdRealField + Copy, const R: usize, const C: usize, const K: usize>
+    ops::Mul<BMatrix<T, C, K>> for BMatrix<T, R, C>
+{
+    type Output = BMatrix<T, R, K>;
+
+    fn mul(self, rhs: BMatrix<T, C, K>) -> Self::Output {
+        BMatrix {
+            data_matrix: self.data_matrix * rhs.data_matrix,
+        }
+    }
+}
+
+impl<T: SimdRealField + Copy, const R: usize, const C: usize> ops::Add for BMatrix<T, R, C> {
+    type Output = BMatrix<T, R, C>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        BMatrix {
+            data_matrix: self.data_matrix + rhs.data_matrix,
+        }
+    }
+}
+
+impl<T: SimdRealField + Copy, const R: usize, const C: usize> ops::Sub for BMatrix<T, R, C> {
+    type Output = BMatrix<T, R, C>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        BMatrix {
+            data_matrix: self.data_matrix - rhs.data_matrix,
+        }
+    }
+}
+
+impl<T: SimdRealField + Copy, const R: usize, const C: usize> ops::Index<(usize, usize)>
+    for BMatrix<T, R, C>
+{
+    type Output = T;
+
+    fn index(&self, index: (usize, usize)) -> &T {
+        &self.data_matrix[index]
+    }
+}
+
+impl<T: SimdRealField + Copy, const R: usize, const C: usize> ops::IndexMut<(usize, usize)>
+    for BMatrix<T, R, C>
+{
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut T {
+        &mut self.data_matrix[index]
+    }
+}
+
+/// Macro to construct a `BMatrix` with compile-time dimensions inferred from the
+/// elements provided. Rows are separated by `;`, like nalgebra's `dmatrix!`.
+///
+/// Example:
+/// - `let m = bmatrix![1.0, 2.0; 3.0, 4.0]; // BMatrix<f64, 2, 2>`
+/// - `let m = bmatrix![1.0, 2.0, 3.0]; // BMatrix<f64, 1, 3>`
+#[macro_export]
+macro_rules! bmatrix {
+    ($($($x:expr),+ $(,)?);+ $(;)?) => {
+        {
+            $crate::BMatrix::from_rows([$([$( $x ),+]),+])
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bvector;
+
+    #[test]
+    fn macro_and_constructors_agree() {
+        let m = bmatrix![1.0, 2.0;
+                         3.0, 4.0;
+                         5.0, 6.0];
+
+        assert_eq!(
+            m,
+            BMatrix::<f64, 3, 2>::from_fn(|row, col| (row * 2 + col + 1) as f64)
+        );
+        assert_eq!((m.nrows(), m.ncols()), (3, 2));
+        assert_eq!(m.get(2, 0), 5.0);
+        assert_eq!(m.row(1), bvector![3.0, 4.0]);
+        assert_eq!(m.column(1), bvector![2.0, 4.0, 6.0]);
+    }
+
+    #[test]
+    fn matrix_products() {
+        let a = bmatrix![1.0, 2.0; 3.0, 4.0];
+        let b = bmatrix![0.0, 1.0; 1.0, 0.0];
+
+        assert_eq!(a.clone() * b.clone(), bmatrix![2.0, 1.0; 4.0, 3.0]);
+        assert_eq!(a.clone() * bvector![1.0, 1.0], bvector![3.0, 7.0]);
+        assert_eq!(a.transpose(), bmatrix![1.0, 3.0; 2.0, 4.0]);
+        assert_eq!(a.clone() - a.clone() + b.clone(), b);
+    }
+
+    #[test]
+    fn element_access() {
+        let mut m = BMatrix::<f32, 2, 3>::from_element(0.0);
+        m[(1, 2)] = 7.5;
+
+        assert_eq!(m[(1, 2)], 7.5);
+        assert_eq!(m.transpose()[(2, 1)], 7.5);
+    }
+}
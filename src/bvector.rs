@@ -1,6 +1,9 @@
-use std::ops;
+use alloc::vec::Vec;
+use core::{fmt, ops};
 
-use nalgebra::{SimdRealField, SVector};
+use nalgebra::{DVector, SVector, SimdRealField};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// This module provides a vector type `BVector` that is optimized for compile-time
 /// dimension and uses SIMD operations for performance.
@@ -27,6 +30,11 @@ impl<T: SimdRealField + Copy, const N: usize> BVector<T, N> {
         }
     }
 
+    /// Constructs the vector by calling `f` with each index.
+    pub fn from_fn(f: impl FnMut(usize) -> T) -> Self {
+        BVector::from_array(core::array::from_fn(f))
+    }
+
     pub fn dot(&self, other: &Self) -> T {
         self.data_vector.dot(&other.data_vector)
     }
@@ -35,10 +43,52 @@ impl<T: SimdRealField + Copy, const N: usize> BVector<T, N> {
         N
     }
 
+    pub fn is_empty(&self) -> bool {
+        N == 0
+    }
+
     pub fn get(&self, index: usize) -> T {
         assert!(index < N, "Index out of bounds");
         self.data_vector[index]
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.data_vector.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.data_vector.iter_mut()
+    }
+
+    /// Returns a vector with `f` applied to every element.
+    pub fn map<U: SimdRealField + Copy>(&self, mut f: impl FnMut(T) -> U) -> BVector<U, N> {
+        BVector::from_fn(|index| f(self.data_vector[index]))
+    }
+
+    /// Returns a vector with `f` applied to every pair of elements of `self` and `other`.
+    pub fn zip_map<U: SimdRealField + Copy>(
+        &self,
+        other: &Self,
+        mut f: impl FnMut(T, T) -> U,
+    ) -> BVector<U, N> {
+        BVector::from_fn(|index| f(self.data_vector[index], other.data_vector[index]))
+    }
+
+    /// Returns the element-wise product of the two vectors.
+    pub fn component_mul(&self, other: &Self) -> Self {
+        BVector {
+            data_vector: self.data_vector.component_mul(&other.data_vector),
+        }
+    }
+
+    /// Returns the Euclidean norm.
+    pub fn norm(&self) -> T {
+        self.data_vector.norm()
+    }
+
+    pub fn sum(&self) -> T {
+        self.data_vector.sum()
+    }
 }
 
 impl<T: SimdRealField + Copy, const N: usize> ops::Add for BVector<T, N> {
@@ -51,6 +101,171 @@ impl<T: SimdRealField + Copy, const N: usize> ops::Add for BVector<T, N> {
     }
 }
 
+impl<T: SimdRealField + Copy, const N: usize> ops::Sub for BVector<T, N> {
+    type Output = BVector<T, N>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        BVector {
+            data_vector: self.data_vector - rhs.data_vector,
+        }
+    }
+}
+
+impl<T: SimdRealField + Copy, const N: usize> ops::Neg for BVector<T, N> {
+    type Output = BVector<T, N>;
+
+    fn neg(self) -> Self::Output {
+        BVector {
+            data_vector: -self.data_vector,
+        }
+    }
+}
+
+impl<T: SimdRealField + Copy, const N: usize> ops::Mul<T> for BVector<T, N> {
+    type Output = BVector<T, N>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        BVector {
+            data_vector: self.data_vector * rhs,
+        }
+    }
+}
+
+impl<T: SimdRealField + Copy, const N: usize> ops::Div<T> for BVector<T, N> {
+    type Output = BVector<T, N>;
+
+    fn div(self, rhs: T) -> Self::Output {
+        BVector {
+            data_vector: self.data_vector / rhs,
+        }
+    }
+}
+
+impl<T: SimdRealField + Copy, const N: usize> ops::AddAssign for BVector<T, N> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.data_vector += rhs.data_vector;
+    }
+}
+
+impl<T: SimdRealField + Copy, const N: usize> ops::SubAssign for BVector<T, N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.data_vector -= rhs.data_vector;
+    }
+}
+
+impl<T: SimdRealField + Copy, const N: usize> ops::MulAssign<T> for BVector<T, N> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.data_vector *= rhs;
+    }
+}
+
+impl<T: SimdRealField + Copy, const N: usize> ops::Index<usize> for BVector<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.data_vector[index]
+    }
+}
+
+impl<T: SimdRealField + Copy, const N: usize> ops::IndexMut<usize> for BVector<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.data_vector[index]
+    }
+}
+
+impl<T: SimdRealField + Copy, const N: usize> From<[T; N]> for BVector<T, N> {
+    fn from(data: [T; N]) -> Self {
+        BVector::from_array(data)
+    }
+}
+
+impl<T: SimdRealField + Copy, const N: usize> From<BVector<T, N>> for [T; N] {
+    fn from(vector: BVector<T, N>) -> Self {
+        vector.data_vector.into()
+    }
+}
+
+/// Error returned when a slice or dynamic vector does not have the length of the
+/// [`BVector`] it is converted into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LengthMismatchError {
+    pub expected: usize,
+    pub got: usize,
+}
+
+impl fmt::Display for LengthMismatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected a vector of {} elements, got {}",
+            self.expected, self.got
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LengthMismatchError {}
+
+impl<T: SimdRealField + Copy, const N: usize> TryFrom<&[T]> for BVector<T, N> {
+    type Error = LengthMismatchError;
+
+    fn try_from(data: &[T]) -> Result<Self, Self::Error> {
+        if data.len() != N {
+            return Err(LengthMismatchError {
+                expected: N,
+                got: data.len(),
+            });
+        }
+        Ok(BVector {
+            data_vector: SVector::<T, N>::from_column_slice(data),
+        })
+    }
+}
+
+impl<T: SimdRealField + Copy, const N: usize> TryFrom<Vec<T>> for BVector<T, N> {
+    type Error = LengthMismatchError;
+
+    fn try_from(data: Vec<T>) -> Result<Self, Self::Error> {
+        BVector::try_from(data.as_slice())
+    }
+}
+
+impl<T: SimdRealField + Copy, const N: usize> TryFrom<DVector<T>> for BVector<T, N> {
+    type Error = LengthMismatchError;
+
+    fn try_from(data: DVector<T>) -> Result<Self, Self::Error> {
+        BVector::try_from(data.as_slice())
+    }
+}
+
+impl<T: SimdRealField + Copy, const N: usize> From<BVector<T, N>> for Vec<T> {
+    fn from(vector: BVector<T, N>) -> Self {
+        vector.data_vector.as_slice().to_vec()
+    }
+}
+
+impl<T: SimdRealField + Copy, const N: usize> From<BVector<T, N>> for DVector<T> {
+    fn from(vector: BVector<T, N>) -> Self {
+        DVector::from_column_slice(vector.data_vector.as_slice())
+    }
+}
+
+/// Serialized as a length-prefixed sequence, so data written for one dimension cannot be
+/// read back as another.
+impl<T: SimdRealField + Copy + Serialize, const N: usize> Serialize for BVector<T, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.data_vector.iter())
+    }
+}
+
+impl<'de, T: SimdRealField + Copy + Deserialize<'de>, const N: usize> Deserialize<'de>
+    for BVector<T, N>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        BVector::try_from(Vec::<T>::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
 /// Macro to construct a `BVector` with compile-time dimension inferred from the
 /// number of elements provided.
 ///
@@ -67,3 +282,86 @@ macro_rules! bvector {
     };
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_operators() {
+        let a = bvector![1.0, 2.0, 3.0];
+        let b = bvector![0.5, -1.0, 2.0];
+
+        assert_eq!(a.clone() - b.clone(), bvector![0.5, 3.0, 1.0]);
+        assert_eq!(-a.clone(), bvector![-1.0, -2.0, -3.0]);
+        assert_eq!(a.clone() * 2.0, bvector![2.0, 4.0, 6.0]);
+        assert_eq!(a.clone() / 2.0, bvector![0.5, 1.0, 1.5]);
+        assert_eq!(a.component_mul(&b), bvector![0.5, -2.0, 6.0]);
+
+        let mut c = a.clone();
+        c += b.clone();
+        c -= a;
+        c *= 4.0;
+        assert_eq!(c, bvector![2.0, -4.0, 8.0]);
+    }
+
+    #[test]
+    fn indexing_and_iteration() {
+        let mut v = bvector![3.0, 4.0];
+        v[1] = 5.0;
+        v.iter_mut().for_each(|x| *x *= 2.0);
+
+        assert_eq!(v[0], 6.0);
+        assert_eq!(v.iter().copied().collect::<Vec<_>>(), vec![6.0, 10.0]);
+    }
+
+    #[test]
+    fn map_reductions_and_constructors() {
+        let v = BVector::<f64, 3>::from_fn(|i| i as f64);
+
+        assert_eq!(v, bvector![0.0, 1.0, 2.0]);
+        assert_eq!(v.map(|x| x * x), bvector![0.0, 1.0, 4.0]);
+        assert_eq!(
+            v.zip_map(&bvector![1.0, 1.0, 1.0], |a, b| a + b),
+            bvector![1.0, 2.0, 3.0]
+        );
+        assert_eq!(v.sum(), 3.0);
+        assert_eq!(bvector![3.0, 4.0].norm(), 5.0);
+    }
+
+    #[test]
+    fn checked_conversions_from_dynamic_vectors() {
+        let from_slice = BVector::<f64, 2>::try_from(&[1.0, 2.0][..]);
+        let from_vec = BVector::<f64, 3>::try_from(vec![1.0, 2.0]);
+        let from_dvector = BVector::<f64, 2>::try_from(DVector::from_vec(vec![1.0, 2.0]));
+
+        assert_eq!(from_slice, Ok(bvector![1.0, 2.0]));
+        assert_eq!(
+            from_vec,
+            Err(LengthMismatchError {
+                expected: 3,
+                got: 2
+            })
+        );
+        assert_eq!(from_dvector, from_slice);
+        assert_eq!(
+            from_vec.unwrap_err().to_string(),
+            "expected a vector of 3 elements, got 2"
+        );
+    }
+
+    #[test]
+    fn infallible_conversions_to_dynamic_vectors() {
+        let v = bvector![1.0, 2.0, 3.0];
+
+        assert_eq!(Vec::from(v.clone()), vec![1.0, 2.0, 3.0]);
+        assert_eq!(DVector::from(v), DVector::from_vec(vec![1.0, 2.0, 3.0]));
+    }
+
+    #[test]
+    fn array_conversions() {
+        let v: BVector<f32, 2> = [1.5, 2.5].into();
+        let array: [f32; 2] = v.into();
+
+        assert_eq!(array, [1.5, 2.5]);
+    }
+}
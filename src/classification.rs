@@ -0,0 +1,271 @@
+use crate::NeuralNetwork;
+
+/// A predicted class, as returned by the classification methods of [`NeuralNetwork`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClassPrediction {
+    pub class: usize,
+    /// The class label from the network metadata, if it labels this class.
+    pub label: Option<String>,
+    pub probability: f64,
+}
+
+/// Turns raw network outputs into class probabilities, as described on
+/// [`NeuralNetwork::predict_proba`].
+fn probabilities(outputs: Vec<f64>) -> Vec<f64> {
+    if let [p] = outputs[..] {
+        let p = p.clamp(0.0, 1.0);
+        return vec![1.0 - p, p];
+    }
+
+    if outputs.iter().all(|&value| value >= 0.0) {
+        let sum: f64 = outputs.iter().sum();
+        if sum == 0.0 {
+            return vec![1.0 / outputs.len() as f64; outputs.len()];
+        }
+        return outputs.iter().map(|value| value / sum).collect();
+    }
+
+    let max = outputs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let exps: Vec<f64> = outputs.iter().map(|value| (value - max).exp()).collect();
+    let sum: f64 = exps.iter().sum();
+    exps.iter().map(|value| value / sum).collect()
+}
+
+impl NeuralNetwork {
+    /// Returns the metadata label of `class` out of `classes`. The labels name one class
+    /// each, except on a single-output network, where two labels name classes 0 and 1 and
+    /// a single label names class 1, the class the output is the probability of.
+    pub(crate) fn class_label(&self, classes: usize, class: usize) -> Option<&str> {
+        let labels = &self.metadata()?.output_labels;
+        match labels.len() {
+            len if len == classes => Some(&labels[class]),
+            1 if classes == 2 && class == 1 => Some(&labels[0]),
+            _ => None,
+        }
+    }
+
+    fn class_prediction(&self, probabilities: &[f64], class: usize) -> ClassPrediction {
+        ClassPrediction {
+            class,
+            label: self
+                .class_label(probabilities.len(), class)
+                .map(str::to_string),
+            probability: probabilities[class],
+        }
+    }
+
+    fn top_k(&self, probabilities: &[f64], k: usize) -> Vec<ClassPrediction> {
+        let mut classes: Vec<usize> = (0..probabilities.len()).collect();
+        classes.sort_by(|&a, &b| probabilities[b].total_cmp(&probabilities[a]));
+
+        classes
+            .into_iter()
+            .take(k)
+            .map(|class| self.class_prediction(probabilities, class))
+            .collect()
+    }
+
+    fn binary(&self, probabilities: &[f64], threshold: f64) -> ClassPrediction {
+        assert_eq!(
+            probabilities.len(),
+            2,
+            "binary prediction needs one or two outputs, the network has {}",
+            probabilities.len()
+        );
+        let class = usize::from(probabilities[1] >= threshold);
+        self.class_prediction(probabilities, class)
+    }
+
+    /// Returns the class probabilities for the given inputs.
+    ///
+    /// A single output is read as the probability of class 1, giving `[1 - p, p]`. Several
+    /// non-negative outputs are divided by their sum, and outputs with negative values go
+    /// through a softmax.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use only_brain::NeuralNetwork;
+    /// let nn = NeuralNetwork::new(&vec![4, 8, 3]);
+    /// let probabilities = nn.predict_proba(&[0.1, 0.2, 0.3, 0.4]);
+    ///
+    /// assert_eq!(probabilities.len(), 3);
+    /// assert!((probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn predict_proba(&self, inputs: &[f64]) -> Vec<f64> {
+        probabilities(self.feed_forward(&inputs.to_vec()))
+    }
+
+    /// Returns the most likely class for the given inputs.
+    pub fn predict_class(&self, inputs: &[f64]) -> ClassPrediction {
+        self.predict_top_k(inputs, 1).remove(0)
+    }
+
+    /// Returns the `k` most likely classes for the given inputs, most likely first.
+    pub fn predict_top_k(&self, inputs: &[f64], k: usize) -> Vec<ClassPrediction> {
+        self.top_k(&self.predict_proba(inputs), k)
+    }
+
+    /// Predicts class 1 when its probability is at least `threshold`, and class 0
+    /// otherwise. The network must have a single output, read as the probability of class
+    /// 1, or two outputs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the network has more than two outputs.
+    pub fn predict_binary(&self, inputs: &[f64], threshold: f64) -> ClassPrediction {
+        self.binary(&self.predict_proba(inputs), threshold)
+    }
+
+    /// Runs [`predict_proba`](Self::predict_proba) over every sample, keeping their order.
+    pub fn predict_proba_many(&self, samples: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        self.feed_forward_many(samples)
+            .into_iter()
+            .map(probabilities)
+            .collect()
+    }
+
+    /// Runs [`predict_class`](Self::predict_class) over every sample, keeping their order.
+    pub fn predict_class_many(&self, samples: &[Vec<f64>]) -> Vec<ClassPrediction> {
+        self.predict_top_k_many(samples, 1)
+            .into_iter()
+            .map(|mut top| top.remove(0))
+            .collect()
+    }
+
+    /// Runs [`predict_top_k`](Self::predict_top_k) over every sample, keeping their order.
+    pub fn predict_top_k_many(&self, samples: &[Vec<f64>], k: usize) -> Vec<Vec<ClassPrediction>> {
+        self.predict_proba_many(samples)
+            .iter()
+            .map(|probabilities| self.top_k(probabilities, k))
+            .collect()
+    }
+
+    /// Runs [`predict_binary`](Self::predict_binary) over every sample, keeping their order.
+    pub fn predict_binary_many(
+        &self,
+        samples: &[Vec<f64>],
+        threshold: f64,
+    ) -> Vec<ClassPrediction> {
+        self.predict_proba_many(samples)
+            .iter()
+            .map(|probabilities| self.binary(probabilities, threshold))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ActivationFunction, ModelMetadata};
+    use nalgebra::{dmatrix, dvector};
+
+    const EPSILON: f64 = 1e-12;
+
+    /// A 2 -> 2 identity network with ReLU, so the outputs equal the inputs.
+    fn identity_network() -> NeuralNetwork {
+        let mut nn = NeuralNetwork::new(&vec![2, 2]);
+        nn.set_activation_function(ActivationFunction::ReLU);
+        nn.set_layer_weights(1, dmatrix![1.0, 0.0; 0.0, 1.0]);
+        nn.set_layer_biases(1, dvector![0.0, 0.0]);
+        nn
+    }
+
+    #[test]
+    fn probabilities_are_normalised_for_every_kind_of_output() {
+        assert_eq!(probabilities(vec![0.75]), vec![0.25, 0.75]);
+        assert_eq!(probabilities(vec![1.0, 3.0]), vec![0.25, 0.75]);
+        assert_eq!(probabilities(vec![0.0, 0.0]), vec![0.5, 0.5]);
+
+        let softmax = probabilities(vec![-1.0, 1.0]);
+        let expected = 1.0 / (1.0 + (-2.0f64).exp());
+        assert!((softmax[1] - expected).abs() < EPSILON);
+        assert!((softmax.iter().sum::<f64>() - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn predict_class_and_top_k_use_the_output_labels() {
+        let mut nn = NeuralNetwork::new(&vec![2, 3]);
+        nn.set_activation_function(ActivationFunction::ReLU);
+        nn.set_layer_weights(1, dmatrix![1.0, 0.0; 0.0, 1.0; 0.5, 0.5]);
+        nn.set_layer_biases(1, dvector![0.0, 0.0, 0.0]);
+        nn.set_metadata(ModelMetadata {
+            output_labels: vec!["setosa".into(), "versicolor".into(), "virginica".into()],
+            ..ModelMetadata::new("iris")
+        });
+
+        let predicted = nn.predict_class(&[1.0, 3.0]);
+        let top = nn.predict_top_k(&[1.0, 3.0], 2);
+
+        assert_eq!(predicted.class, 1);
+        assert_eq!(predicted.label.as_deref(), Some("versicolor"));
+        assert!((predicted.probability - 0.5).abs() < EPSILON);
+        assert_eq!(top.iter().map(|p| p.class).collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn predict_binary_honours_the_threshold() {
+        let nn = identity_network();
+
+        assert_eq!(nn.predict_binary(&[0.7, 0.3], 0.5).class, 0);
+        assert_eq!(nn.predict_binary(&[0.7, 0.3], 0.25).class, 1);
+        assert_eq!(nn.predict_binary(&[0.7, 0.3], 0.25).label, None);
+    }
+
+    #[test]
+    fn binary_classifiers_with_a_single_output_get_their_labels() {
+        let mut nn = NeuralNetwork::new(&vec![1, 1]);
+        nn.set_activation_function(ActivationFunction::ReLU);
+        nn.set_layer_weights(1, dmatrix![1.0]);
+        nn.set_layer_biases(1, dvector![0.0]);
+        nn.set_metadata(ModelMetadata {
+            output_labels: vec!["ham".into(), "spam".into()],
+            ..ModelMetadata::new("spam")
+        });
+
+        assert_eq!(
+            nn.predict_binary(&[0.8], 0.5).label.as_deref(),
+            Some("spam")
+        );
+        assert_eq!(nn.predict_class(&[0.2]).label.as_deref(), Some("ham"));
+
+        nn.set_metadata(ModelMetadata {
+            output_labels: vec!["spam".into()],
+            ..ModelMetadata::new("spam")
+        });
+
+        assert_eq!(
+            nn.predict_binary(&[0.8], 0.5).label.as_deref(),
+            Some("spam")
+        );
+        assert_eq!(nn.predict_binary(&[0.2], 0.5).label, None);
+    }
+
+    #[test]
+    fn batch_versions_match_single_sample_predictions() {
+        let nn = identity_network();
+        let samples = vec![vec![0.2, 0.8], vec![0.9, 0.1], vec![0.5, 0.5]];
+
+        let classes = nn.predict_class_many(&samples);
+        let binary = nn.predict_binary_many(&samples, 0.6);
+
+        for (index, sample) in samples.iter().enumerate() {
+            assert_eq!(classes[index], nn.predict_class(sample));
+            assert_eq!(binary[index], nn.predict_binary(sample, 0.6));
+        }
+        assert_eq!(
+            nn.predict_proba_many(&samples)[1],
+            nn.predict_proba(&samples[1])
+        );
+        assert_eq!(
+            nn.predict_top_k_many(&samples, 2)[0],
+            nn.predict_top_k(&samples[0], 2)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "binary prediction needs one or two outputs")]
+    fn predict_binary_rejects_multiclass_networks() {
+        NeuralNetwork::new(&vec![2, 3]).predict_binary(&[0.0, 0.0], 0.5);
+    }
+}
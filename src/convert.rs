@@ -0,0 +1,27 @@
+use nalgebra::{DMatrix, DVector};
+
+/// Types that [`NeuralNetwork::set_layer_weights`] accepts as a weights matrix.
+///
+/// [`NeuralNetwork::set_layer_weights`]: crate::NeuralNetwork::set_layer_weights
+pub trait IntoWeightsMatrix {
+    fn into_weights_matrix(self) -> DMatrix<f64>;
+}
+
+/// Types that [`NeuralNetwork::set_layer_biases`] accepts as a biases vector.
+///
+/// [`NeuralNetwork::set_layer_biases`]: crate::NeuralNetwork::set_layer_biases
+pub trait IntoBiasVector {
+    fn into_bias_vector(self) -> DVector<f64>;
+}
+
+impl IntoWeightsMatrix for DMatrix<f64> {
+    fn into_weights_matrix(self) -> DMatrix<f64> {
+        self
+    }
+}
+
+impl IntoBiasVector for DVector<f64> {
+    fn into_bias_vector(self) -> DVector<f64> {
+        self
+    }
+}
@@ -0,0 +1,221 @@
+use std::error::Error;
+use std::fmt;
+
+use polars::prelude::*;
+
+use crate::{Dataset, NeuralNetwork};
+
+/// Error returned when a Polars [`DataFrame`] cannot be used as network input.
+#[derive(Debug)]
+pub enum DataFrameError {
+    Polars(PolarsError),
+    /// The column has null values, which have no numeric input to map to.
+    NullValues {
+        column: String,
+        count: usize,
+    },
+    NonNumericColumn {
+        column: String,
+        dtype: DataType,
+    },
+    /// The number of feature columns does not match the input layer size.
+    ColumnCountMismatch {
+        expected: usize,
+        got: usize,
+    },
+}
+
+impl fmt::Display for DataFrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataFrameError::Polars(error) => write!(f, "{}", error),
+            DataFrameError::NullValues { column, count } => {
+                write!(f, "column `{}` has {} null values", column, count)
+            }
+            DataFrameError::NonNumericColumn { column, dtype } => {
+                write!(
+                    f,
+                    "column `{}` is not numeric, it has type {}",
+                    column, dtype
+                )
+            }
+            DataFrameError::ColumnCountMismatch { expected, got } => {
+                write!(
+                    f,
+                    "the network takes {} inputs but {} feature columns were given",
+                    expected, got
+                )
+            }
+        }
+    }
+}
+
+impl Error for DataFrameError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DataFrameError::Polars(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<PolarsError> for DataFrameError {
+    fn from(error: PolarsError) -> Self {
+        DataFrameError::Polars(error)
+    }
+}
+
+/// Reads the given numeric columns as one row per DataFrame row.
+fn rows_from_columns(df: &DataFrame, names: &[&str]) -> Result<Vec<Vec<f64>>, DataFrameError> {
+    let mut columns = Vec::with_capacity(names.len());
+
+    for &name in names {
+        let column = df.column(name)?;
+        if !column.dtype().is_primitive_numeric() {
+            return Err(DataFrameError::NonNumericColumn {
+                column: name.to_string(),
+                dtype: column.dtype().clone(),
+            });
+        }
+        if column.null_count() > 0 {
+            return Err(DataFrameError::NullValues {
+                column: name.to_string(),
+                count: column.null_count(),
+            });
+        }
+        let values: Vec<f64> = column
+            .cast(&DataType::Float64)?
+            .f64()?
+            .into_no_null_iter()
+            .collect();
+        columns.push(values);
+    }
+
+    Ok((0..df.height())
+        .map(|row| columns.iter().map(|column| column[row]).collect())
+        .collect())
+}
+
+impl NeuralNetwork {
+    /// Runs the network over every row of `df`, reading the inputs from `feature_cols`, and
+    /// returns `df` with one `output_<i>` column appended per output neuron.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use only_brain::NeuralNetwork;
+    /// use polars::prelude::*;
+    ///
+    /// let df = df!("x" => [0.5, 0.1], "y" => [0.2, 0.9]).unwrap();
+    /// let nn = NeuralNetwork::new(&vec![2, 3, 1]);
+    ///
+    /// let scored = nn.predict_df(&df, &["x", "y"]).unwrap();
+    /// assert_eq!(scored.get_column_names(), &["x", "y", "output_0"]);
+    /// ```
+    pub fn predict_df(
+        &self,
+        df: &DataFrame,
+        feature_cols: &[&str],
+    ) -> Result<DataFrame, DataFrameError> {
+        if feature_cols.len() != self.layer_size(0) {
+            return Err(DataFrameError::ColumnCountMismatch {
+                expected: self.layer_size(0),
+                got: feature_cols.len(),
+            });
+        }
+
+        let outputs = self.feed_forward_many(&rows_from_columns(df, feature_cols)?);
+        let output_columns: Vec<Column> = (0..self.layer_size(self.num_layers() - 1))
+            .map(|neuron| {
+                let values: Vec<f64> = outputs.iter().map(|row| row[neuron]).collect();
+                Column::new(format!("output_{}", neuron).into(), values)
+            })
+            .collect();
+
+        Ok(df.hstack(&output_columns)?)
+    }
+}
+
+impl Dataset {
+    /// Builds a dataset from the `features` and `targets` columns of `df`. Every selected
+    /// column must be numeric and free of nulls.
+    pub fn from_df(
+        df: &DataFrame,
+        features: &[&str],
+        targets: &[&str],
+    ) -> Result<Dataset, DataFrameError> {
+        let inputs = rows_from_columns(df, features)?;
+        let target_rows = rows_from_columns(df, targets)?;
+
+        Ok(Dataset::new(inputs, target_rows).with_names(
+            features.iter().map(|name| name.to_string()).collect(),
+            targets.iter().map(|name| name.to_string()).collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{dmatrix, dvector};
+
+    fn network() -> NeuralNetwork {
+        let mut nn = NeuralNetwork::new(&vec![2, 2]);
+        nn.set_layer_weights(1, dmatrix![0.5, -0.25; 1.0, 1.0]);
+        nn.set_layer_biases(1, dvector![0.1, 0.0]);
+        nn
+    }
+
+    #[test]
+    fn predict_df_appends_one_column_per_output() {
+        let df = df!("a" => [1i32, 0], "b" => [2.0f32, 0.5], "id" => ["x", "y"]).unwrap();
+        let nn = network();
+
+        let scored = nn.predict_df(&df, &["a", "b"]).unwrap();
+
+        assert_eq!(scored.width(), 5);
+        let expected = nn.feed_forward(&vec![1.0, 2.0]);
+        let output_1 = scored.column("output_1").unwrap().f64().unwrap();
+        assert_eq!(output_1.get(0), Some(expected[1]));
+    }
+
+    #[test]
+    fn predict_df_rejects_the_wrong_number_of_columns() {
+        let df = df!("a" => [1.0]).unwrap();
+
+        let error = network().predict_df(&df, &["a"]).unwrap_err();
+
+        assert!(matches!(
+            error,
+            DataFrameError::ColumnCountMismatch {
+                expected: 2,
+                got: 1
+            }
+        ));
+    }
+
+    #[test]
+    fn nulls_and_non_numeric_columns_are_rejected() {
+        let df = df!("a" => [Some(1.0), None], "b" => [1.0, 2.0], "s" => ["x", "y"]).unwrap();
+
+        let nulls = Dataset::from_df(&df, &["a"], &["b"]).unwrap_err();
+        let strings = Dataset::from_df(&df, &["b"], &["s"]).unwrap_err();
+        let missing = Dataset::from_df(&df, &["nope"], &["b"]).unwrap_err();
+
+        assert!(matches!(nulls, DataFrameError::NullValues { count: 1, .. }));
+        assert!(matches!(strings, DataFrameError::NonNumericColumn { .. }));
+        assert!(matches!(missing, DataFrameError::Polars(_)));
+        assert_eq!(nulls.to_string(), "column `a` has 1 null values");
+    }
+
+    #[test]
+    fn from_df_reads_features_and_targets() {
+        let df = df!("a" => [1.0, 2.0], "b" => [3i64, 4], "t" => [0u32, 1]).unwrap();
+
+        let dataset = Dataset::from_df(&df, &["b", "a"], &["t"]).unwrap();
+
+        assert_eq!(dataset.inputs(), &[vec![3.0, 1.0], vec![4.0, 2.0]]);
+        assert_eq!(dataset.targets(), &[vec![0.0], vec![1.0]]);
+        assert_eq!(dataset.target_names(), &["t"]);
+    }
+}
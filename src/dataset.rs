@@ -0,0 +1,349 @@
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+/// Input and target rows for running and evaluating a network.
+///
+/// Rows are `Vec<f64>`, the same shape [`NeuralNetwork::feed_forward`] takes and returns,
+/// so a dataset can be fed to a network directly.
+///
+/// # Example
+///
+/// ```
+/// # use only_brain::{Dataset, NeuralNetwork};
+/// let mut dataset = Dataset::new(
+///     vec![vec![0.0, 0.0], vec![0.0, 1.0], vec![1.0, 0.0], vec![1.0, 1.0]],
+///     vec![vec![0.0], vec![1.0], vec![1.0], vec![0.0]],
+/// );
+/// dataset.shuffle(42);
+/// let (train, test) = dataset.train_test_split(0.25);
+///
+/// let nn = NeuralNetwork::new(&vec![2, 2, 1]);
+/// for batch in train.batches(2) {
+///     let outputs = nn.feed_forward_many(batch.inputs);
+///     assert_eq!(outputs.len(), batch.targets.len());
+/// }
+/// assert_eq!(test.len(), 1);
+/// ```
+///
+/// [`NeuralNetwork::feed_forward`]: crate::NeuralNetwork::feed_forward
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Dataset {
+    inputs: Vec<Vec<f64>>,
+    targets: Vec<Vec<f64>>,
+    feature_names: Vec<String>,
+    target_names: Vec<String>,
+}
+
+/// A contiguous slice of a [`Dataset`], as yielded by [`Dataset::batches`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Batch<'a> {
+    pub inputs: &'a [Vec<f64>],
+    pub targets: &'a [Vec<f64>],
+}
+
+impl Dataset {
+    /// Creates a dataset from one input and one target row per sample.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are not as many target rows as input rows, or if the rows are not
+    /// all the same width.
+    pub fn new(inputs: Vec<Vec<f64>>, targets: Vec<Vec<f64>>) -> Self {
+        assert_eq!(
+            inputs.len(),
+            targets.len(),
+            "got {} input rows but {} target rows",
+            inputs.len(),
+            targets.len()
+        );
+        for rows in [&inputs, &targets] {
+            if let Some(first) = rows.first() {
+                assert!(
+                    rows.iter().all(|row| row.len() == first.len()),
+                    "every row must have the same number of columns"
+                );
+            }
+        }
+
+        Self {
+            inputs,
+            targets,
+            feature_names: Vec::new(),
+            target_names: Vec::new(),
+        }
+    }
+
+    /// Names the input and target columns, e.g. after building a dataset by hand.
+    pub fn with_names(mut self, feature_names: Vec<String>, target_names: Vec<String>) -> Self {
+        self.feature_names = feature_names;
+        self.target_names = target_names;
+        self
+    }
+
+    /// Loads a dataset from a CSV file with a header row. The `features` columns become
+    /// the inputs and the `targets` columns the targets, in the order given. Other
+    /// columns are ignored.
+    ///
+    /// Every selected field must be a number. Fields may be wrapped in double quotes, but
+    /// quoted commas are not supported.
+    pub fn from_csv(
+        path: &str,
+        features: &[&str],
+        targets: &[&str],
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut lines = BufReader::new(File::open(path)?).lines();
+        let header = lines.next().ok_or("the CSV file is empty")??;
+        let columns: Vec<&str> = header.split(',').map(unquote).collect();
+
+        let position = |name: &&str| {
+            columns
+                .iter()
+                .position(|column| column == name)
+                .ok_or_else(|| format!("column `{}` not found in the CSV header", name))
+        };
+        let feature_columns = features
+            .iter()
+            .map(position)
+            .collect::<Result<Vec<_>, _>>()?;
+        let target_columns = targets
+            .iter()
+            .map(position)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut inputs = Vec::new();
+        let mut target_rows = Vec::new();
+        for (index, line) in lines.enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let fields: Vec<&str> = line.split(',').map(unquote).collect();
+            let parse = |&column: &usize| -> Result<f64, Box<dyn Error>> {
+                let field = fields.get(column).ok_or_else(|| {
+                    format!("line {}: missing column `{}`", index + 2, columns[column])
+                })?;
+                field.parse::<f64>().map_err(|_| {
+                    format!(
+                        "line {}: column `{}` is not a number: `{}`",
+                        index + 2,
+                        columns[column],
+                        field
+                    )
+                    .into()
+                })
+            };
+            inputs.push(
+                feature_columns
+                    .iter()
+                    .map(parse)
+                    .collect::<Result<Vec<_>, _>>()?,
+            );
+            target_rows.push(
+                target_columns
+                    .iter()
+                    .map(parse)
+                    .collect::<Result<Vec<_>, _>>()?,
+            );
+        }
+
+        Ok(Self::new(inputs, target_rows).with_names(
+            features.iter().map(|name| name.to_string()).collect(),
+            targets.iter().map(|name| name.to_string()).collect(),
+        ))
+    }
+
+    pub fn inputs(&self) -> &[Vec<f64>] {
+        &self.inputs
+    }
+
+    pub fn targets(&self) -> &[Vec<f64>] {
+        &self.targets
+    }
+
+    pub fn feature_names(&self) -> &[String] {
+        &self.feature_names
+    }
+
+    pub fn target_names(&self) -> &[String] {
+        &self.target_names
+    }
+
+    /// Returns the number of samples.
+    pub fn len(&self) -> usize {
+        self.inputs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+    }
+
+    /// Iterates over `(input, target)` pairs.
+    pub fn iter(&self) -> impl Iterator<Item = (&Vec<f64>, &Vec<f64>)> {
+        self.inputs.iter().zip(&self.targets)
+    }
+
+    /// Shuffles the samples. The same seed always gives the same order.
+    pub fn shuffle(&mut self, seed: u64) {
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        order.shuffle(&mut StdRng::seed_from_u64(seed));
+
+        self.inputs = order.iter().map(|&i| self.inputs[i].clone()).collect();
+        self.targets = order.iter().map(|&i| self.targets[i].clone()).collect();
+    }
+
+    /// Splits off the last `test_fraction` of the samples as a test set. Shuffle first
+    /// if the samples are ordered.
+    pub fn train_test_split(&self, test_fraction: f64) -> (Dataset, Dataset) {
+        let (train, test, _) = self.train_test_validation_split(test_fraction, 0.0);
+        (train, test)
+    }
+
+    /// Splits the samples, in order, into train, test and validation sets. The test and
+    /// validation sizes are rounded to whole samples and the rest goes to training. If the
+    /// rounding asks for more samples than there are, validation gets what is left after
+    /// the test set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the fractions are negative or add up to more than 1.
+    pub fn train_test_validation_split(
+        &self,
+        test_fraction: f64,
+        validation_fraction: f64,
+    ) -> (Dataset, Dataset, Dataset) {
+        assert!(
+            test_fraction >= 0.0
+                && validation_fraction >= 0.0
+                && test_fraction + validation_fraction <= 1.0,
+            "split fractions must be non-negative and add up to at most 1"
+        );
+        let test = (self.len() as f64 * test_fraction).round() as usize;
+        let validation =
+            ((self.len() as f64 * validation_fraction).round() as usize).min(self.len() - test);
+        let train = self.len() - test - validation;
+
+        (
+            self.slice(0, train),
+            self.slice(train, train + test),
+            self.slice(train + test, self.len()),
+        )
+    }
+
+    fn slice(&self, start: usize, end: usize) -> Dataset {
+        Dataset {
+            inputs: self.inputs[start..end].to_vec(),
+            targets: self.targets[start..end].to_vec(),
+            feature_names: self.feature_names.clone(),
+            target_names: self.target_names.clone(),
+        }
+    }
+
+    /// Iterates over the samples in mini-batches of `size`. The last batch is smaller if
+    /// the samples do not divide evenly.
+    pub fn batches(&self, size: usize) -> impl Iterator<Item = Batch<'_>> {
+        assert!(size > 0, "batch size must be at least 1");
+        self.inputs
+            .chunks(size)
+            .zip(self.targets.chunks(size))
+            .map(|(inputs, targets)| Batch { inputs, targets })
+    }
+}
+
+fn unquote(field: &str) -> &str {
+    let field = field.trim();
+    field
+        .strip_prefix('"')
+        .and_then(|f| f.strip_suffix('"'))
+        .unwrap_or(field)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbered(count: usize) -> Dataset {
+        Dataset::new(
+            (0..count).map(|i| vec![i as f64]).collect(),
+            (0..count).map(|i| vec![i as f64 * 10.0]).collect(),
+        )
+    }
+
+    #[test]
+    fn from_csv_selects_named_columns_in_order() {
+        let path =
+            std::env::temp_dir().join(format!("only-brain-dataset-{}.csv", std::process::id()));
+        std::fs::write(&path, "id,\"b\",a,label\n1,0.5,1.5,1\n2,-2,3,0\n").unwrap();
+
+        let dataset = Dataset::from_csv(path.to_str().unwrap(), &["a", "b"], &["label"]);
+        std::fs::remove_file(&path).unwrap();
+        let dataset = dataset.unwrap();
+
+        assert_eq!(dataset.inputs(), &[vec![1.5, 0.5], vec![3.0, -2.0]]);
+        assert_eq!(dataset.targets(), &[vec![1.0], vec![0.0]]);
+        assert_eq!(dataset.feature_names(), &["a", "b"]);
+    }
+
+    #[test]
+    fn from_csv_reports_missing_and_non_numeric_columns() {
+        let path =
+            std::env::temp_dir().join(format!("only-brain-dataset-bad-{}.csv", std::process::id()));
+        std::fs::write(&path, "a,b\n1,oops\n").unwrap();
+
+        let missing = Dataset::from_csv(path.to_str().unwrap(), &["a"], &["c"]).unwrap_err();
+        let non_numeric = Dataset::from_csv(path.to_str().unwrap(), &["a"], &["b"]).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(missing.to_string().contains("column `c` not found"));
+        assert!(non_numeric
+            .to_string()
+            .contains("line 2: column `b` is not a number"));
+    }
+
+    #[test]
+    fn shuffle_is_reproducible_and_keeps_pairs_together() {
+        let mut a = numbered(20);
+        let mut b = numbered(20);
+        a.shuffle(7);
+        b.shuffle(7);
+
+        assert_eq!(a, b);
+        assert_ne!(a, numbered(20));
+        assert!(a.iter().all(|(input, target)| target[0] == input[0] * 10.0));
+    }
+
+    #[test]
+    fn splits_cover_every_sample_once() {
+        let (train, test, validation) = numbered(10).train_test_validation_split(0.2, 0.1);
+
+        assert_eq!((train.len(), test.len(), validation.len()), (7, 2, 1));
+        assert_eq!(test.inputs(), &[vec![7.0], vec![8.0]]);
+        assert_eq!(validation.inputs(), &[vec![9.0]]);
+    }
+
+    /// 0.5 of 3 samples rounds up to 2 for both sets, one more than there is.
+    #[test]
+    fn rounding_up_both_fractions_does_not_overflow_the_samples() {
+        let (train, test, validation) = numbered(3).train_test_validation_split(0.5, 0.5);
+
+        assert_eq!((train.len(), test.len(), validation.len()), (0, 2, 1));
+    }
+
+    #[test]
+    fn batches_keep_the_remainder() {
+        let dataset = numbered(5);
+        let sizes: Vec<usize> = dataset.batches(2).map(|batch| batch.inputs.len()).collect();
+
+        assert_eq!(sizes, vec![2, 2, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "got 2 input rows but 1 target rows")]
+    fn new_rejects_mismatched_row_counts() {
+        Dataset::new(vec![vec![1.0], vec![2.0]], vec![vec![1.0]]);
+    }
+}
@@ -0,0 +1,236 @@
+use std::fmt;
+
+use crate::activation_functions::ActivationFunction;
+use crate::layer::Layer;
+use crate::NeuralNetwork;
+
+/// How far the weights and biases of two same-shaped layers are apart.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LayerDelta {
+    pub max_weight_delta: f64,
+    pub mean_weight_delta: f64,
+    pub max_bias_delta: f64,
+    pub mean_bias_delta: f64,
+    /// Euclidean distance between the two layers, weights and biases together.
+    pub l2_distance: f64,
+}
+
+/// Comparison of the layer at index `layer` in two networks.
+///
+/// Shapes are `(neurons, inputs)`, and are `None` when the network does not have that
+/// layer at all. `delta` is only computed when both shapes are present and equal.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayerDiff {
+    pub layer: usize,
+    pub shape_a: Option<(usize, usize)>,
+    pub shape_b: Option<(usize, usize)>,
+    pub delta: Option<LayerDelta>,
+}
+
+impl LayerDiff {
+    pub fn shapes_match(&self) -> bool {
+        self.shape_a.is_some() && self.shape_a == self.shape_b
+    }
+}
+
+/// Report produced by [`diff`]. Its `Display` implementation prints one row per layer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NetworkDiff {
+    pub activation_a: ActivationFunction,
+    pub activation_b: ActivationFunction,
+    pub layers: Vec<LayerDiff>,
+}
+
+impl NetworkDiff {
+    /// Returns `true` if both networks have the same activation function and every layer
+    /// has the same shape.
+    pub fn shapes_match(&self) -> bool {
+        self.activation_a == self.activation_b && self.layers.iter().all(LayerDiff::shapes_match)
+    }
+
+    /// Returns the largest absolute weight or bias delta over all comparable layers.
+    pub fn max_delta(&self) -> f64 {
+        self.layers
+            .iter()
+            .filter_map(|layer| layer.delta)
+            .map(|delta| delta.max_weight_delta.max(delta.max_bias_delta))
+            .fold(0.0, f64::max)
+    }
+}
+
+/// Compares two networks layer by layer.
+///
+/// Layers are matched by index, so a network with an extra layer shows up as a row with
+/// one missing shape.
+///
+/// # Example
+///
+/// ```
+/// # use only_brain::{diff, NeuralNetwork};
+/// let nn = NeuralNetwork::new(&vec![2, 3, 1]);
+/// let mut retrained = nn.clone();
+/// retrained.set_weight(2, 0, 1, nn.get_weight(2, 0, 1) + 0.5);
+///
+/// let report = diff(&nn, &retrained);
+/// assert!(report.shapes_match());
+/// assert!((report.max_delta() - 0.5).abs() < 1e-12);
+/// println!("{}", report);
+/// ```
+pub fn diff(a: &NeuralNetwork, b: &NeuralNetwork) -> NetworkDiff {
+    let count = a.layers().len().max(b.layers().len());
+
+    let layers = (0..count)
+        .map(|index| {
+            let layer_a = a.layers().get(index);
+            let layer_b = b.layers().get(index);
+            let shape_a = layer_a.map(Layer::shape);
+            let shape_b = layer_b.map(Layer::shape);
+
+            let delta = match (layer_a, layer_b) {
+                (Some(la), Some(lb)) if shape_a == shape_b => Some(layer_delta(la, lb)),
+                _ => None,
+            };
+
+            LayerDiff {
+                layer: index + 1,
+                shape_a,
+                shape_b,
+                delta,
+            }
+        })
+        .collect();
+
+    NetworkDiff {
+        activation_a: a.activation_function(),
+        activation_b: b.activation_function(),
+        layers,
+    }
+}
+
+fn layer_delta(a: &Layer, b: &Layer) -> LayerDelta {
+    let weights = (a.weights() - b.weights()).abs();
+    let biases = (a.biases() - b.biases()).abs();
+
+    LayerDelta {
+        max_weight_delta: weights.max(),
+        mean_weight_delta: weights.mean(),
+        max_bias_delta: biases.max(),
+        mean_bias_delta: biases.mean(),
+        l2_distance: (weights.norm_squared() + biases.norm_squared()).sqrt(),
+    }
+}
+
+fn format_shape(shape: Option<(usize, usize)>) -> String {
+    match shape {
+        Some((neurons, inputs)) => format!("{}x{}", neurons, inputs),
+        None => "-".to_string(),
+    }
+}
+
+impl fmt::Display for NetworkDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Activation Function: {:?} vs {:?}",
+            self.activation_a, self.activation_b
+        )?;
+        writeln!(
+            f,
+            "{:<6} {:>9} {:>9} {:>10} {:>10} {:>10} {:>10} {:>10}",
+            "Layer", "Shape A", "Shape B", "Max |dW|", "Mean |dW|", "Max |db|", "Mean |db|", "L2"
+        )?;
+        for layer in &self.layers {
+            write!(
+                f,
+                "{:<6} {:>9} {:>9}",
+                layer.layer,
+                format_shape(layer.shape_a),
+                format_shape(layer.shape_b)
+            )?;
+            match layer.delta {
+                Some(delta) => writeln!(
+                    f,
+                    " {:>10.6} {:>10.6} {:>10.6} {:>10.6} {:>10.6}",
+                    delta.max_weight_delta,
+                    delta.mean_weight_delta,
+                    delta.max_bias_delta,
+                    delta.mean_bias_delta,
+                    delta.l2_distance
+                )?,
+                None => writeln!(f, " {:>10}", "shape mismatch")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{dmatrix, dvector};
+
+    const EPSILON: f64 = 1e-12;
+
+    fn network() -> NeuralNetwork {
+        let mut nn = NeuralNetwork::new(&vec![2, 1]);
+        nn.set_layer_weights(1, dmatrix![0.5, -0.25]);
+        nn.set_layer_biases(1, dvector![0.1]);
+        nn
+    }
+
+    #[test]
+    fn identical_networks_have_zero_delta() {
+        let nn = network();
+        let report = diff(&nn, &nn.clone());
+
+        assert!(report.shapes_match());
+        assert_eq!(report.max_delta(), 0.0);
+        assert_eq!(report.layers[0].delta.unwrap().l2_distance, 0.0);
+    }
+
+    #[test]
+    fn deltas_are_reported_per_layer() {
+        let a = network();
+        let mut b = network();
+        b.set_layer_weights(1, dmatrix![0.8, -0.25]);
+        b.set_layer_biases(1, dvector![-0.3]);
+
+        let delta = diff(&a, &b).layers[0].delta.unwrap();
+
+        assert!((delta.max_weight_delta - 0.3).abs() < EPSILON);
+        assert!((delta.mean_weight_delta - 0.15).abs() < EPSILON);
+        assert!((delta.max_bias_delta - 0.4).abs() < EPSILON);
+        assert!((delta.mean_bias_delta - 0.4).abs() < EPSILON);
+        assert!((delta.l2_distance - 0.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn shape_differences_are_reported_without_deltas() {
+        let a = NeuralNetwork::new(&vec![2, 3, 1]);
+        let b = NeuralNetwork::new(&vec![2, 4]);
+
+        let report = diff(&a, &b);
+
+        assert!(!report.shapes_match());
+        assert_eq!(report.layers.len(), 2);
+        assert_eq!(report.layers[0].shape_a, Some((3, 2)));
+        assert_eq!(report.layers[0].shape_b, Some((4, 2)));
+        assert_eq!(report.layers[1].shape_b, None);
+        assert!(report.layers.iter().all(|layer| layer.delta.is_none()));
+    }
+
+    #[test]
+    fn display_prints_a_row_per_layer() {
+        let rendered = diff(&network(), &NeuralNetwork::new(&vec![2, 2])).to_string();
+
+        assert!(
+            rendered.contains("Max |dW|"),
+            "unexpected output:\n{rendered}"
+        );
+        assert!(rendered.contains("1x2"), "unexpected output:\n{rendered}");
+        assert!(
+            rendered.contains("shape mismatch"),
+            "unexpected output:\n{rendered}"
+        );
+    }
+}
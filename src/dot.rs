@@ -0,0 +1,309 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use nalgebra::{DMatrix, DVector};
+
+use crate::activation_functions::ActivationFunction;
+use crate::{NeuralNetwork, Perceptron};
+
+/// Options for the Graphviz export of [`NeuralNetwork::to_dot_with`] and
+/// [`Perceptron::to_dot_with`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DotOptions {
+    /// Edges whose absolute weight is below this value are not drawn.
+    pub edge_threshold: f64,
+    /// Layers with more neurons than this are drawn as a single summarised node.
+    pub collapse_above: Option<usize>,
+    /// Whether edges are labelled with their weight.
+    pub show_weights: bool,
+}
+
+impl Default for DotOptions {
+    fn default() -> Self {
+        Self {
+            edge_threshold: 0.0,
+            collapse_above: Some(32),
+            show_weights: true,
+        }
+    }
+}
+
+/// A dense layer as seen by the exporter: `(neurons x inputs)` weights and one bias per
+/// neuron.
+struct DotLayer<'a> {
+    weights: &'a DMatrix<f64>,
+    biases: &'a DVector<f64>,
+}
+
+/// Edges between two nodes, before drawing. Collapsed layers merge many connections into
+/// one edge, so this keeps enough to summarise them.
+#[derive(Default)]
+struct EdgeGroup {
+    count: usize,
+    sum: f64,
+    sum_abs: f64,
+}
+
+fn node_id(layer: usize, neuron: usize, collapsed: bool) -> String {
+    if collapsed {
+        format!("l{}", layer)
+    } else {
+        format!("l{}_n{}", layer, neuron)
+    }
+}
+
+fn render(
+    input_size: usize,
+    layers: &[DotLayer],
+    activation: ActivationFunction,
+    options: &DotOptions,
+) -> String {
+    let sizes: Vec<usize> = std::iter::once(input_size)
+        .chain(layers.iter().map(|layer| layer.biases.len()))
+        .collect();
+    let collapsed: Vec<bool> = sizes
+        .iter()
+        .map(|&size| options.collapse_above.is_some_and(|limit| size > limit))
+        .collect();
+    let max_abs = layers
+        .iter()
+        .map(|layer| layer.weights.amax())
+        .fold(0.0, f64::max);
+
+    let mut dot = String::new();
+    let _ = writeln!(dot, "digraph network {{");
+    let _ = writeln!(dot, "  rankdir=LR;");
+    let _ = writeln!(dot, "  splines=line;");
+    let _ = writeln!(
+        dot,
+        "  node [shape=circle, fixedsize=true, width=0.6, fontsize=10];"
+    );
+
+    for (index, &size) in sizes.iter().enumerate() {
+        let title = if index == 0 {
+            format!("Input ({})", size)
+        } else {
+            format!("Layer {} ({}, {:?})", index, size, activation)
+        };
+        let _ = writeln!(dot, "  subgraph cluster_{} {{", index);
+        let _ = writeln!(dot, "    label=\"{}\";", title);
+        let _ = writeln!(dot, "    color=lightgrey;");
+
+        if collapsed[index] {
+            let detail = if index == 0 {
+                format!("{} inputs", size)
+            } else {
+                let biases = layers[index - 1].biases;
+                format!("{} neurons\\nmean b={:.2}", size, biases.mean())
+            };
+            let _ = writeln!(
+                dot,
+                "    {} [shape=box, fixedsize=false, label=\"{}\"];",
+                node_id(index, 0, true),
+                detail
+            );
+        } else {
+            for neuron in 0..size {
+                let label = if index == 0 {
+                    format!("x{}", neuron)
+                } else {
+                    format!("b={:.2}", layers[index - 1].biases[neuron])
+                };
+                let _ = writeln!(
+                    dot,
+                    "    {} [label=\"{}\"];",
+                    node_id(index, neuron, false),
+                    label
+                );
+            }
+        }
+        let _ = writeln!(dot, "  }}");
+    }
+
+    for (index, layer) in layers.iter().enumerate() {
+        let mut groups: BTreeMap<(String, String), EdgeGroup> = BTreeMap::new();
+
+        for neuron in 0..layer.weights.nrows() {
+            for input in 0..layer.weights.ncols() {
+                let weight = layer.weights[(neuron, input)];
+                if weight.abs() < options.edge_threshold {
+                    continue;
+                }
+                let from = node_id(index, input, collapsed[index]);
+                let to = node_id(index + 1, neuron, collapsed[index + 1]);
+                let group = groups.entry((from, to)).or_default();
+                group.count += 1;
+                group.sum += weight;
+                group.sum_abs += weight.abs();
+            }
+        }
+
+        for ((from, to), group) in groups {
+            let _ = writeln!(
+                dot,
+                "  {} -> {} [{}];",
+                from,
+                to,
+                edge_attributes(&group, max_abs, options)
+            );
+        }
+    }
+
+    let _ = writeln!(dot, "}}");
+    dot
+}
+
+fn edge_attributes(group: &EdgeGroup, max_abs: f64, options: &DotOptions) -> String {
+    let magnitude = group.sum_abs / group.count as f64;
+    let penwidth = if max_abs > 0.0 {
+        0.5 + 3.5 * magnitude / max_abs
+    } else {
+        0.5
+    };
+
+    if group.count == 1 {
+        let color = if group.sum < 0.0 {
+            "firebrick"
+        } else {
+            "steelblue"
+        };
+        let label = if options.show_weights {
+            format!(", label=\"{:.2}\"", group.sum)
+        } else {
+            String::new()
+        };
+        format!("color={}, penwidth={:.2}{}", color, penwidth, label)
+    } else {
+        let label = if options.show_weights {
+            format!(
+                ", label=\"{} weights\\nmean |w|={:.2}\"",
+                group.count, magnitude
+            )
+        } else {
+            String::new()
+        };
+        format!("color=gray40, penwidth={:.2}{}", penwidth, label)
+    }
+}
+
+impl NeuralNetwork {
+    /// Renders the network topology as a Graphviz `digraph` with the default
+    /// [`DotOptions`].
+    ///
+    /// Each layer is a cluster with one node per neuron. Edges are blue for positive and
+    /// red for negative weights, and thicker the larger their magnitude.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use only_brain::NeuralNetwork;
+    /// let nn = NeuralNetwork::new(&vec![2, 3, 1]);
+    /// let dot = nn.to_dot();
+    ///
+    /// assert!(dot.starts_with("digraph network {"));
+    /// std::fs::write(std::env::temp_dir().join("network.dot"), dot).unwrap();
+    /// ```
+    pub fn to_dot(&self) -> String {
+        self.to_dot_with(&DotOptions::default())
+    }
+
+    /// Renders the network topology as a Graphviz `digraph` using the given options.
+    pub fn to_dot_with(&self, options: &DotOptions) -> String {
+        let layers: Vec<DotLayer> = self
+            .layers()
+            .iter()
+            .map(|layer| DotLayer {
+                weights: layer.weights(),
+                biases: layer.biases(),
+            })
+            .collect();
+
+        render(
+            self.layer_size(0),
+            &layers,
+            self.activation_function(),
+            options,
+        )
+    }
+}
+
+impl<const N: usize> Perceptron<f64, N> {
+    /// Renders the perceptron as a Graphviz `digraph` with the default [`DotOptions`].
+    pub fn to_dot(&self) -> String {
+        self.to_dot_with(&DotOptions::default())
+    }
+
+    /// Renders the perceptron as a Graphviz `digraph` using the given options.
+    pub fn to_dot_with(&self, options: &DotOptions) -> String {
+        let weights = DMatrix::from_row_slice(1, N, self.weights().data_vector.as_slice());
+        let biases = DVector::from_element(1, self.bias());
+        let layer = DotLayer {
+            weights: &weights,
+            biases: &biases,
+        };
+
+        render(N, &[layer], self.activation_function(), options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bvector;
+    use nalgebra::{dmatrix, dvector};
+
+    fn network() -> NeuralNetwork {
+        let mut nn = NeuralNetwork::new(&vec![2, 1]);
+        nn.set_layer_weights(1, dmatrix![0.5, -0.01]);
+        nn.set_layer_biases(1, dvector![0.1]);
+        nn
+    }
+
+    #[test]
+    fn every_neuron_and_connection_is_drawn() {
+        let dot = network().to_dot();
+
+        assert!(dot.contains("l0_n0 [label=\"x0\"]"), "{dot}");
+        assert!(dot.contains("l1_n0 [label=\"b=0.10\"]"), "{dot}");
+        assert!(dot.contains("l0_n0 -> l1_n0 [color=steelblue"), "{dot}");
+        assert!(dot.contains("l0_n1 -> l1_n0 [color=firebrick"), "{dot}");
+    }
+
+    #[test]
+    fn edges_below_the_threshold_are_hidden() {
+        let options = DotOptions {
+            edge_threshold: 0.1,
+            ..DotOptions::default()
+        };
+        let dot = network().to_dot_with(&options);
+
+        assert!(dot.contains("l0_n0 -> l1_n0"), "{dot}");
+        assert!(!dot.contains("l0_n1 -> l1_n0"), "{dot}");
+    }
+
+    #[test]
+    fn large_layers_collapse_into_one_node() {
+        let nn = NeuralNetwork::new(&vec![100, 3]);
+        let options = DotOptions {
+            collapse_above: Some(10),
+            ..DotOptions::default()
+        };
+        let dot = nn.to_dot_with(&options);
+
+        assert!(dot.contains("l0 [shape=box"), "{dot}");
+        assert!(!dot.contains("l0_n0"), "{dot}");
+        assert_eq!(dot.matches(" -> ").count(), 3, "{dot}");
+        assert!(dot.contains("100 weights"), "{dot}");
+    }
+
+    #[test]
+    fn perceptrons_render_as_a_single_layer() {
+        let mut perceptron = Perceptron::<f64, 2>::new(ActivationFunction::BinaryStep);
+        perceptron.set_weights(bvector![1.0, -1.0]);
+
+        let dot = perceptron.to_dot();
+
+        assert!(dot.contains("Layer 1 (1, BinaryStep)"), "{dot}");
+        assert_eq!(dot.matches(" -> ").count(), 2, "{dot}");
+    }
+}
@@ -0,0 +1,370 @@
+//! C ABI for loading and running models from C and C++.
+//!
+//! Every fallible function returns an [`ObStatus`]. When it is not [`ObStatus::Ok`], a
+//! description of the error can be read with [`ob_last_error_message`]. The header is
+//! `include/only_brain.h`, generated with `cbindgen --config cbindgen.toml`.
+
+use std::cell::RefCell;
+use std::error::Error;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+
+use crate::{load_model, load_model_from_bytes, NeuralNetwork};
+
+/// Status code returned by the fallible functions of the C API.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ObStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidPath = 2,
+    Io = 3,
+    InvalidModel = 4,
+    SizeMismatch = 5,
+    Panic = 6,
+}
+
+/// Opaque handle to a loaded model. Create it with [`ob_load_model`] or
+/// [`ob_load_model_from_buffer`] and release it with [`ob_free_model`].
+pub struct ObModel {
+    network: NeuralNetwork,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: impl Into<String>) {
+    let message = CString::new(message.into().replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+fn fail(status: ObStatus, message: impl Into<String>) -> ObStatus {
+    set_last_error(message);
+    status
+}
+
+/// Failing to open the file is an I/O error. Anything the decoder rejects, including
+/// data that ends too early, is an invalid model.
+fn load_error_status(error: &(dyn Error + 'static)) -> ObStatus {
+    if error.is::<std::io::Error>() {
+        ObStatus::Io
+    } else {
+        ObStatus::InvalidModel
+    }
+}
+
+/// Runs `body`, turning a panic into [`ObStatus::Panic`] so it never unwinds into C.
+fn guard(body: impl FnOnce() -> ObStatus) -> ObStatus {
+    guard_or(ObStatus::Panic, body)
+}
+
+/// Like [`guard`] for functions that return a value instead of a status: a panic is
+/// recorded as the last error and `fallback` is returned.
+fn guard_or<T>(fallback: T, body: impl FnOnce() -> T) -> T {
+    match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(value) => value,
+        Err(_) => {
+            set_last_error("panicked inside only-brain");
+            fallback
+        }
+    }
+}
+
+fn store_model(
+    loaded: Result<NeuralNetwork, Box<dyn Error>>,
+    out_model: *mut *mut ObModel,
+) -> ObStatus {
+    match loaded {
+        Ok(network) => {
+            let model = Box::new(ObModel { network });
+            // SAFETY: the caller checked `out_model` for null and guarantees it is writable.
+            unsafe { *out_model = Box::into_raw(model) };
+            ObStatus::Ok
+        }
+        Err(error) => {
+            let status = load_error_status(error.as_ref());
+            fail(status, format!("failed to load model: {}", error))
+        }
+    }
+}
+
+/// Returns a description of the last error on the calling thread, or null if there was
+/// none. The string stays valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn ob_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// Loads a model written by `dump_model` from the file at `path`.
+///
+/// # Safety
+///
+/// `path` must be a valid NUL-terminated string and `out_model` must point to writable
+/// memory for one pointer.
+#[no_mangle]
+pub unsafe extern "C" fn ob_load_model(
+    path: *const c_char,
+    out_model: *mut *mut ObModel,
+) -> ObStatus {
+    guard(|| {
+        if path.is_null() || out_model.is_null() {
+            return fail(ObStatus::NullPointer, "path and out_model must not be null");
+        }
+        let path = match unsafe { CStr::from_ptr(path) }.to_str() {
+            Ok(path) => path,
+            Err(_) => return fail(ObStatus::InvalidPath, "path is not valid UTF-8"),
+        };
+
+        store_model(load_model(path), out_model)
+    })
+}
+
+/// Loads a model from the `len` bytes at `data`, in the format written by `dump_model`.
+///
+/// # Safety
+///
+/// `data` must point to `len` readable bytes and `out_model` must point to writable
+/// memory for one pointer.
+#[no_mangle]
+pub unsafe extern "C" fn ob_load_model_from_buffer(
+    data: *const u8,
+    len: usize,
+    out_model: *mut *mut ObModel,
+) -> ObStatus {
+    guard(|| {
+        if data.is_null() || out_model.is_null() {
+            return fail(ObStatus::NullPointer, "data and out_model must not be null");
+        }
+        let bytes = unsafe { slice::from_raw_parts(data, len) };
+
+        store_model(load_model_from_bytes(bytes), out_model)
+    })
+}
+
+/// Returns the number of layers of the model, including the input layer, or 0 if
+/// `model` is null or the call panicked.
+///
+/// # Safety
+///
+/// `model` must be null or a pointer returned by one of the load functions that has not
+/// been freed.
+#[no_mangle]
+pub unsafe extern "C" fn ob_num_layers(model: *const ObModel) -> usize {
+    guard_or(0, || match unsafe { model.as_ref() } {
+        Some(model) => model.network.num_layers(),
+        None => 0,
+    })
+}
+
+/// Returns the number of neurons of the given layer, or 0 if `model` is null, the
+/// model has no layers, the layer does not exist or the call panicked. Layer 0 is the
+/// input layer. Except for a null `model`, the reason is recorded as the last error.
+///
+/// # Safety
+///
+/// Same as [`ob_num_layers`].
+#[no_mangle]
+pub unsafe extern "C" fn ob_layer_size(model: *const ObModel, layer: usize) -> usize {
+    guard_or(0, || {
+        let Some(model) = (unsafe { model.as_ref() }) else {
+            return 0;
+        };
+        let num_layers = model.network.num_layers();
+        if num_layers < 2 {
+            set_last_error("the model has no layers");
+            0
+        } else if layer >= num_layers {
+            set_last_error(format!(
+                "layer {} does not exist, the model has {} layers",
+                layer, num_layers
+            ));
+            0
+        } else {
+            model.network.layer_size(layer)
+        }
+    })
+}
+
+/// Feeds `num_inputs` values to the model and writes the outputs to `outputs`.
+/// `num_inputs` must equal the input layer size and `num_outputs` the output layer size.
+///
+/// # Safety
+///
+/// `model` must be a live model handle, `inputs` must point to `num_inputs` readable
+/// doubles and `outputs` to `num_outputs` writable doubles.
+#[no_mangle]
+pub unsafe extern "C" fn ob_feed_forward(
+    model: *const ObModel,
+    inputs: *const f64,
+    num_inputs: usize,
+    outputs: *mut f64,
+    num_outputs: usize,
+) -> ObStatus {
+    guard(|| {
+        let model = match unsafe { model.as_ref() } {
+            Some(model) if !inputs.is_null() && !outputs.is_null() => model,
+            _ => {
+                return fail(
+                    ObStatus::NullPointer,
+                    "model, inputs and outputs must not be null",
+                )
+            }
+        };
+        let network = &model.network;
+
+        let expected_inputs = network.layer_size(0);
+        let expected_outputs = network.layer_size(network.num_layers() - 1);
+        if num_inputs != expected_inputs || num_outputs != expected_outputs {
+            return fail(
+                ObStatus::SizeMismatch,
+                format!(
+                    "model takes {} inputs and produces {} outputs, got buffers of {} and {}",
+                    expected_inputs, expected_outputs, num_inputs, num_outputs
+                ),
+            );
+        }
+
+        let inputs = unsafe { slice::from_raw_parts(inputs, num_inputs) }.to_vec();
+        let result = network.feed_forward(&inputs);
+        unsafe { slice::from_raw_parts_mut(outputs, num_outputs) }.copy_from_slice(&result);
+
+        ObStatus::Ok
+    })
+}
+
+/// Releases a model. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `model` must be null or a pointer returned by one of the load functions, and must not
+/// be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn ob_free_model(model: *mut ObModel) {
+    if !model.is_null() {
+        drop(unsafe { Box::from_raw(model) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ActivationFunction;
+    use nalgebra::{dmatrix, dvector};
+
+    fn encoded_network() -> (NeuralNetwork, Vec<u8>) {
+        let mut nn = NeuralNetwork::new(&vec![2, 1]);
+        nn.set_layer_weights(1, dmatrix![0.5, -0.25]);
+        nn.set_layer_biases(1, dvector![0.1]);
+        let bytes = crate::dump_model_to_bytes(&nn).unwrap();
+        (nn, bytes)
+    }
+
+    fn last_error() -> String {
+        let message = ob_last_error_message();
+        assert!(!message.is_null());
+        unsafe { CStr::from_ptr(message) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn a_model_loaded_from_a_buffer_runs_like_the_original() {
+        let (nn, bytes) = encoded_network();
+        let mut model = ptr::null_mut();
+
+        unsafe {
+            assert_eq!(
+                ob_load_model_from_buffer(bytes.as_ptr(), bytes.len(), &mut model),
+                ObStatus::Ok
+            );
+            assert_eq!(ob_num_layers(model), 2);
+            assert_eq!(ob_layer_size(model, 0), 2);
+            assert_eq!(ob_layer_size(model, 1), 1);
+            assert_eq!(ob_layer_size(model, 2), 0);
+            assert_eq!(
+                last_error(),
+                "layer 2 does not exist, the model has 2 layers"
+            );
+
+            let inputs = [1.0, 2.0];
+            let mut outputs = [0.0];
+            let status = ob_feed_forward(model, inputs.as_ptr(), 2, outputs.as_mut_ptr(), 1);
+            assert_eq!(status, ObStatus::Ok);
+            assert_eq!(outputs.to_vec(), nn.feed_forward(&inputs.to_vec()));
+
+            ob_free_model(model);
+        }
+    }
+
+    #[test]
+    fn wrong_buffer_sizes_are_reported() {
+        let (_, bytes) = encoded_network();
+        let mut model = ptr::null_mut();
+
+        unsafe {
+            ob_load_model_from_buffer(bytes.as_ptr(), bytes.len(), &mut model);
+            let inputs = [1.0, 2.0, 3.0];
+            let mut outputs = [0.0];
+            let status = ob_feed_forward(model, inputs.as_ptr(), 3, outputs.as_mut_ptr(), 1);
+
+            assert_eq!(status, ObStatus::SizeMismatch);
+            assert!(last_error().contains("takes 2 inputs"));
+            ob_free_model(model);
+        }
+    }
+
+    #[test]
+    fn models_without_layers_are_rejected_at_load_time() {
+        let empty = NeuralNetwork::from_layers(Vec::new(), ActivationFunction::Sigmoid);
+        let bytes = crate::dump_model_to_bytes(&empty).unwrap();
+        let mut model = ptr::null_mut();
+
+        unsafe {
+            let status = ob_load_model_from_buffer(bytes.as_ptr(), bytes.len(), &mut model);
+            assert_eq!(status, ObStatus::InvalidModel);
+        }
+        assert!(model.is_null());
+        assert!(last_error().contains("the model has no layers"));
+    }
+
+    #[test]
+    fn layer_queries_on_a_model_without_layers_report_it() {
+        let empty = NeuralNetwork::from_layers(Vec::new(), ActivationFunction::Sigmoid);
+        let model = Box::into_raw(Box::new(ObModel { network: empty }));
+
+        unsafe {
+            assert_eq!(ob_num_layers(model), 1);
+            assert_eq!(ob_layer_size(model, 0), 0);
+            ob_free_model(model);
+        }
+        assert_eq!(last_error(), "the model has no layers");
+    }
+
+    #[test]
+    fn load_failures_map_to_status_codes() {
+        let mut model = ptr::null_mut();
+        let garbage = b"not a model";
+        let missing = CString::new("/nonexistent/only-brain-model.bin").unwrap();
+
+        unsafe {
+            assert_eq!(
+                ob_load_model_from_buffer(garbage.as_ptr(), garbage.len(), &mut model),
+                ObStatus::InvalidModel
+            );
+            assert_eq!(ob_load_model(missing.as_ptr(), &mut model), ObStatus::Io);
+            assert_eq!(
+                ob_load_model(ptr::null(), &mut model),
+                ObStatus::NullPointer
+            );
+        }
+        assert!(model.is_null());
+        assert!(last_error().contains("must not be null"));
+    }
+}
@@ -1,9 +1,52 @@
 use std::fs::File;
 use std::io::Write;
-use crate::NeuralNetwork;
+
+use bincode::Options;
+use nalgebra::RealField;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::neural_network::LegacyNeuralNetwork;
+use crate::{NeuralNetwork, Perceptron, Pipeline, QuantizedNetwork, SparseNetwork};
+
+/// Model files start with this tag, followed by the format version as a little-endian
+/// `u32` and the bincode-encoded network. Bincode is not self-describing, so the version
+/// says which layout follows.
+const MODEL_MAGIC: &[u8; 4] = b"OBNN";
+
+/// Version 2 added the input transform and the metadata. Files without a header are
+/// version 1: only the layers and the activation function.
+const MODEL_FORMAT_VERSION: u32 = 2;
+
+/// Pipeline files have the same header as model files, under their own tag.
+const PIPELINE_MAGIC: &[u8; 4] = b"OBPL";
+
+const PIPELINE_FORMAT_VERSION: u32 = 1;
+
+fn header(magic: &[u8; 4], version: u32) -> Vec<u8> {
+    let mut encoded = magic.to_vec();
+    encoded.extend_from_slice(&version.to_le_bytes());
+    encoded
+}
+
+/// Splits the format version off the rest of a header that followed its tag.
+fn split_version(bytes: &[u8]) -> Result<(u32, &[u8]), Box<dyn std::error::Error>> {
+    let (version, body) = bytes
+        .split_at_checked(4)
+        .ok_or("the file header is truncated")?;
+    Ok((u32::from_le_bytes(version.try_into()?), body))
+}
+
+/// Encodes the model in the format of [`dump_model`].
+pub fn dump_model_to_bytes(model: &NeuralNetwork) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut encoded = header(MODEL_MAGIC, MODEL_FORMAT_VERSION);
+    bincode::serialize_into(&mut encoded, model)?;
+
+    Ok(encoded)
+}
 
 pub fn dump_model(model: &NeuralNetwork, path: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let encoded = bincode::serialize(model)?;
+    let encoded = dump_model_to_bytes(model)?;
 
     let mut file = File::create(path)?;
     file.write_all(&encoded)?;
@@ -11,9 +54,133 @@ pub fn dump_model(model: &NeuralNetwork, path: &str) -> Result<(), Box<dyn std::
     Ok(())
 }
 
+/// Loads a model saved by [`dump_model`], including files written before the format
+/// header was introduced.
 pub fn load_model(path: &str) -> Result<NeuralNetwork, Box<dyn std::error::Error>> {
+    load_model_from_bytes(&std::fs::read(path)?)
+}
+
+/// Decodes a model in the format of [`dump_model`]. Fails if the layers do not fit
+/// together, see [`NeuralNetwork::check_structure`].
+pub fn load_model_from_bytes(bytes: &[u8]) -> Result<NeuralNetwork, Box<dyn std::error::Error>> {
+    let model = decode_model(bytes)?;
+    model.check_structure()?;
+
+    Ok(model)
+}
+
+/// Decodes bytes written by `bincode::serialize`. Unlike `bincode::deserialize`, leftover
+/// bytes are an error: they mean the data is not in the layout being decoded.
+fn deserialize_exact<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, bincode::Error> {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .deserialize(bytes)
+}
+
+fn decode_model(bytes: &[u8]) -> Result<NeuralNetwork, Box<dyn std::error::Error>> {
+    let Some(body) = bytes.strip_prefix(MODEL_MAGIC) else {
+        let legacy: LegacyNeuralNetwork = deserialize_exact(bytes)?;
+        return Ok(legacy.into());
+    };
+    let (version, body) = split_version(body)?;
+
+    match version {
+        MODEL_FORMAT_VERSION => Ok(deserialize_exact(body)?),
+        version => Err(format!("unsupported model format version {}", version).into()),
+    }
+}
+
+/// Writes `value` to `path` as plain bincode, the format of every file but models and
+/// pipelines.
+fn dump<T: Serialize>(value: &T, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let encoded = bincode::serialize(value)?;
+
+    let mut file = File::create(path)?;
+    file.write_all(&encoded)?;
+
+    Ok(())
+}
+
+/// Reads a value written by [`dump`].
+fn load<T: DeserializeOwned>(path: &str) -> Result<T, Box<dyn std::error::Error>> {
     let file = File::open(path)?;
-    let model = bincode::deserialize_from(file)?;
+    let value = bincode::deserialize_from(file)?;
+
+    Ok(value)
+}
+
+pub fn dump_quantized_model(
+    model: &QuantizedNetwork,
+    path: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    dump(model, path)
+}
+
+/// Loads a model saved by [`dump_quantized_model`]. Fails if the stored layers are not
+/// consistent with their shapes, instead of panicking when the model is run.
+pub fn load_quantized_model(path: &str) -> Result<QuantizedNetwork, Box<dyn std::error::Error>> {
+    let model: QuantizedNetwork = load(path)?;
+    model.check()?;
+
+    Ok(model)
+}
+
+pub fn dump_sparse_model(
+    model: &SparseNetwork,
+    path: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    dump(model, path)
+}
+
+/// Loads a model saved by [`dump_sparse_model`]. Fails if the stored layers are not
+/// consistent with their shapes, instead of panicking when the model is run.
+pub fn load_sparse_model(path: &str) -> Result<SparseNetwork, Box<dyn std::error::Error>> {
+    let model: SparseNetwork = load(path)?;
+    model.check()?;
 
     Ok(model)
 }
+
+/// Saves the pipeline as one file, with a format header like [`dump_model`].
+pub fn dump_pipeline(pipeline: &Pipeline, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let mut encoded = header(PIPELINE_MAGIC, PIPELINE_FORMAT_VERSION);
+    bincode::serialize_into(&mut encoded, pipeline)?;
+
+    let mut file = File::create(path)?;
+    file.write_all(&encoded)?;
+
+    Ok(())
+}
+
+/// Loads a pipeline saved by [`dump_pipeline`]. Fails if any of its steps would have been
+/// rejected when the pipeline was built.
+pub fn load_pipeline(path: &str) -> Result<Pipeline, Box<dyn std::error::Error>> {
+    let bytes = std::fs::read(path)?;
+    let body = bytes
+        .strip_prefix(PIPELINE_MAGIC)
+        .ok_or("the file is not a saved pipeline")?;
+    let pipeline: Pipeline = match split_version(body)? {
+        (PIPELINE_FORMAT_VERSION, body) => deserialize_exact(body)?,
+        (version, _) => {
+            return Err(format!("unsupported pipeline format version {}", version).into())
+        }
+    };
+    pipeline.check()?;
+
+    Ok(pipeline)
+}
+
+pub fn dump_perceptron<T: RealField + Copy + Serialize, const N: usize>(
+    perceptron: &Perceptron<T, N>,
+    path: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    dump(perceptron, path)
+}
+
+/// Loads a perceptron saved by [`dump_perceptron`]. Fails if it was saved with a different
+/// number of inputs than `N`. The element type `T` must be the one it was saved with.
+pub fn load_perceptron<T: RealField + Copy + DeserializeOwned, const N: usize>(
+    path: &str,
+) -> Result<Perceptron<T, N>, Box<dyn std::error::Error>> {
+    load(path)
+}
@@ -1,10 +1,13 @@
-use std::fmt;
+use core::fmt;
 use nalgebra::{DMatrix, DVector};
 use rand::distr::Uniform;
 use rand::Rng;
+#[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize)]
+// nalgebra only implements serde for heap-allocated matrices with `std`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Layer {
     size: usize,
     weights: DMatrix<f64>,
@@ -18,14 +21,39 @@ impl Layer {
 
         Self {
             size: neurons,
-            weights: DMatrix::from_fn(neurons, inputs, |_, _| rng.sample(&uniform)),
+            weights: DMatrix::from_fn(neurons, inputs, |_, _| rng.sample(uniform)),
             bias: DVector::from_element(neurons, 0.0),
         }
     }
 
+    /// Builds a layer from a neurons x inputs weights matrix and one bias per neuron.
+    pub fn from_parts(weights: DMatrix<f64>, bias: DVector<f64>) -> Self {
+        if bias.nrows() != weights.nrows() {
+            panic!("Incompatible biases vector size");
+        }
+        Self {
+            size: weights.nrows(),
+            weights,
+            bias,
+        }
+    }
+
     pub fn forward(&self, inputs: &DVector<f64>, activation_func: fn(f64) -> f64) -> DVector<f64> {
         let outputs = &self.weights * inputs + &self.bias;
-        outputs.map(|x| activation_func(x))
+        outputs.map(activation_func)
+    }
+
+    /// Forwards a batch of samples, one per row of `inputs`.
+    pub fn forward_batch(
+        &self,
+        inputs: &DMatrix<f64>,
+        activation_func: fn(f64) -> f64,
+    ) -> DMatrix<f64> {
+        let mut outputs = inputs * self.weights.transpose();
+        for mut row in outputs.row_iter_mut() {
+            row += self.bias.transpose();
+        }
+        outputs.map(activation_func)
     }
 
     pub fn set_weight(&mut self, neuron: usize, input: usize, weight: f64) {
@@ -57,6 +85,25 @@ impl Layer {
     pub fn weights(&self) -> &DMatrix<f64> {
         &self.weights
     }
+
+    /// Returns the `(neurons, inputs)` shape of the weights matrix.
+    pub fn shape(&self) -> (usize, usize) {
+        self.weights.shape()
+    }
+
+    pub fn approx_eq(&self, other: &Self, epsilon: f64) -> bool {
+        self.shape() == other.shape()
+            && self
+                .weights
+                .iter()
+                .zip(other.weights.iter())
+                .all(|(a, b)| (a - b).abs() <= epsilon)
+            && self
+                .bias
+                .iter()
+                .zip(other.bias.iter())
+                .all(|(a, b)| (a - b).abs() <= epsilon)
+    }
 }
 
 impl fmt::Display for Layer {
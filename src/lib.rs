@@ -1,4 +1,3 @@
-
 //! # Only Brain
 //!
 //! A very simple Neural Network library built in Rust with the objective to allow
@@ -29,17 +28,100 @@
 //!     println!("{:?}", output);
 //! }
 //! ```
-mod neural_network;
-mod layer;
+//!
+//! # Features
+//!
+//! - `std` (default): model I/O, network analysis and `NeuralNetwork::new`, which seeds
+//!   its weights from the thread-local random number generator. Without it the crate is
+//!   `#![no_std]` and only needs `alloc`, which is enough to run a trained network on a
+//!   microcontroller.
+//! - `parallel`: spreads batch inference over a rayon thread pool.
+//! - `polars`: run networks over Polars DataFrames and build datasets from them.
+//! - `ndarray`: `ndarray` arrays as inputs, outputs and layer weights.
+//! - `ffi`: a C ABI in [`ffi`], declared in `include/only_brain.h`. Build the shared
+//!   library with `cargo rustc --release --lib --features ffi --crate-type cdylib`.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 mod activation_functions;
+mod layer;
+mod neural_network;
 mod perceptron;
+mod perceptron_layer;
 
-mod io;
+mod bmatrix;
 mod bvector;
+#[cfg(feature = "std")]
+mod classification;
+mod convert;
+#[cfg(feature = "polars")]
+mod dataframe;
+#[cfg(feature = "std")]
+mod dataset;
+#[cfg(feature = "std")]
+mod diff;
+#[cfg(feature = "std")]
+mod dot;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "std")]
+mod linear_models;
+mod metadata;
+#[cfg(feature = "std")]
+pub mod metrics;
+#[cfg(feature = "ndarray")]
+mod ndarray_support;
+#[cfg(feature = "std")]
+mod pipeline;
+mod preprocessing;
+#[cfg(feature = "std")]
+mod pruning;
+#[cfg(feature = "std")]
+mod quantization;
+#[cfg(feature = "std")]
+mod sparse;
+#[cfg(feature = "std")]
+mod summary;
+#[cfg(feature = "std")]
+mod training;
 
+pub use activation_functions::*;
+pub use bmatrix::*;
+pub use bvector::*;
+#[cfg(feature = "std")]
+pub use classification::*;
+pub use convert::*;
+#[cfg(feature = "polars")]
+pub use dataframe::*;
+#[cfg(feature = "std")]
+pub use dataset::*;
+#[cfg(feature = "std")]
+pub use diff::*;
+#[cfg(feature = "std")]
+pub use dot::*;
+#[cfg(feature = "std")]
 pub use io::*;
+#[cfg(feature = "std")]
+pub use linear_models::*;
+pub use metadata::*;
+#[cfg(feature = "std")]
+pub use metrics::{ClassificationReport, ConfusionMatrix, RegressionReport};
 pub use neural_network::*;
 pub use perceptron::*;
-pub use activation_functions::*;
-pub use bvector::*;
-
+pub use perceptron_layer::*;
+#[cfg(feature = "std")]
+pub use pipeline::*;
+pub use preprocessing::*;
+#[cfg(feature = "std")]
+pub use pruning::*;
+#[cfg(feature = "std")]
+pub use quantization::*;
+#[cfg(feature = "std")]
+pub use sparse::*;
+#[cfg(feature = "std")]
+pub use summary::*;
+#[cfg(feature = "std")]
+pub use training::*;
@@ -0,0 +1,484 @@
+use core::fmt;
+
+use crate::training::{check_labels, run_epochs};
+use crate::{ActivationFunction, BVector, Perceptron, TrainOptions, TrainingHistory};
+
+/// Moves `weights` and `bias` by `step` in the direction of `sample`.
+fn update<const N: usize>(
+    weights: &mut BVector<f64, N>,
+    bias: &mut f64,
+    sample: &BVector<f64, N>,
+    step: f64,
+) {
+    *weights += sample.clone() * step;
+    *bias += step;
+}
+
+fn misclassified<const N: usize>(
+    perceptron: &Perceptron<f64, N>,
+    samples: &[BVector<f64, N>],
+    labels: &[f64],
+) -> usize {
+    samples
+        .iter()
+        .zip(labels)
+        .filter(|(sample, &label)| perceptron.feed_forward(sample) != label)
+        .count()
+}
+
+fn step_perceptron<const N: usize>() -> Perceptron<f64, N> {
+    Perceptron::new(ActivationFunction::BinaryStep)
+}
+
+/// Adaptive linear neuron. It is trained with the delta rule
+/// `w += eta * (target - w·x - b) * x` on its linear output, and classifies a sample as
+/// `1.0` when that output is at least 0.5.
+///
+/// The delta rule minimises the squared error, so it also settles on data that is not
+/// linearly separable. Scale the features first, e.g. with a
+/// [`StandardScaler`](crate::StandardScaler), or the updates can diverge.
+///
+/// Unlike [`Perceptron`], it only works on `f64`.
+///
+/// # Example
+///
+/// ```
+/// # use only_brain::{bvector, Adaline};
+/// let samples = [bvector![0.0, 0.0], bvector![0.0, 1.0], bvector![1.0, 0.0], bvector![1.0, 1.0]];
+/// let labels = [0.0, 0.0, 0.0, 1.0];
+///
+/// let mut adaline = Adaline::<2>::new();
+/// adaline.train(&samples, &labels, 0.05, 200);
+///
+/// assert_eq!(adaline.predict(&bvector![1.0, 1.0]), 1.0);
+/// assert_eq!(adaline.predict(&bvector![0.0, 1.0]), 0.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Adaline<const N: usize> {
+    weights: BVector<f64, N>,
+    bias: f64,
+}
+
+impl<const N: usize> Default for Adaline<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Adaline<N> {
+    pub fn new() -> Self {
+        Self {
+            weights: BVector::from_element(0.0),
+            bias: 0.0,
+        }
+    }
+
+    pub fn weights(&self) -> &BVector<f64, N> {
+        &self.weights
+    }
+
+    pub fn bias(&self) -> f64 {
+        self.bias
+    }
+
+    /// Returns the linear output `w·x + b`.
+    pub fn output(&self, inputs: &BVector<f64, N>) -> f64 {
+        self.weights.dot(inputs) + self.bias
+    }
+
+    /// Returns `1.0` if the linear output is at least 0.5, and `0.0` otherwise.
+    pub fn predict(&self, inputs: &BVector<f64, N>) -> f64 {
+        if self.output(inputs) >= 0.5 {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Trains with the delta rule, one sample at a time. Labels are `0.0` or `1.0`, and the
+    /// history counts the samples [`predict`](Self::predict) got wrong before each update.
+    pub fn train(
+        &mut self,
+        samples: &[BVector<f64, N>],
+        labels: &[f64],
+        eta: f64,
+        epochs: usize,
+    ) -> TrainingHistory {
+        self.train_with(
+            samples,
+            labels,
+            &TrainOptions {
+                eta,
+                epochs,
+                ..TrainOptions::default()
+            },
+        )
+    }
+
+    /// Trains like [`train`](Self::train), with the given options.
+    pub fn train_with(
+        &mut self,
+        samples: &[BVector<f64, N>],
+        labels: &[f64],
+        options: &TrainOptions,
+    ) -> TrainingHistory {
+        check_labels(samples.len(), labels.len());
+
+        run_epochs(samples.len(), options, |index| {
+            let (sample, target) = (&samples[index], labels[index]);
+            let wrong = self.predict(sample) != target;
+            let step = options.eta * (target - self.output(sample));
+            update(&mut self.weights, &mut self.bias, sample, step);
+            wrong
+        })
+    }
+}
+
+impl<const N: usize> fmt::Display for Adaline<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Adaline:")?;
+        writeln!(f)?;
+        writeln!(f, "Inputs Size: {}", N)?;
+        writeln!(f, "Weights: {:?}", self.weights)?;
+        writeln!(f, "Bias: {}", self.bias)?;
+        writeln!(f)?;
+
+        Ok(())
+    }
+}
+
+/// Perceptron trained with the Pocket algorithm: it runs the perceptron rule, and keeps
+/// "in its pocket" the weights that misclassified the fewest training samples. Unlike a
+/// plain perceptron, the result does not depend on where training stopped when the data
+/// is not linearly separable.
+///
+/// The pocket holds a `Perceptron<f64, N>`, so samples and labels are `f64`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PocketPerceptron<const N: usize> {
+    pocket: Perceptron<f64, N>,
+    misclassified: Option<usize>,
+}
+
+impl<const N: usize> Default for PocketPerceptron<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> PocketPerceptron<N> {
+    pub fn new() -> Self {
+        Self {
+            pocket: step_perceptron(),
+            misclassified: None,
+        }
+    }
+
+    /// Returns the best perceptron found so far.
+    pub fn perceptron(&self) -> &Perceptron<f64, N> {
+        &self.pocket
+    }
+
+    /// Returns how many training samples the pocket weights misclassify, once trained.
+    pub fn misclassified(&self) -> Option<usize> {
+        self.misclassified
+    }
+
+    pub fn feed_forward(&self, inputs: &BVector<f64, N>) -> f64 {
+        self.pocket.feed_forward(inputs)
+    }
+
+    /// Trains with the perceptron rule, starting from the pocket weights. At the end of
+    /// every epoch the current weights replace the pocket if they misclassify fewer
+    /// samples.
+    pub fn train(
+        &mut self,
+        samples: &[BVector<f64, N>],
+        labels: &[f64],
+        eta: f64,
+        epochs: usize,
+    ) -> TrainingHistory {
+        self.train_with(
+            samples,
+            labels,
+            &TrainOptions {
+                eta,
+                epochs,
+                ..TrainOptions::default()
+            },
+        )
+    }
+
+    /// Trains like [`train`](Self::train), with the given options.
+    pub fn train_with(
+        &mut self,
+        samples: &[BVector<f64, N>],
+        labels: &[f64],
+        options: &TrainOptions,
+    ) -> TrainingHistory {
+        check_labels(samples.len(), labels.len());
+        let mut current = self.pocket.clone();
+        let mut best = misclassified(&self.pocket, samples, labels);
+
+        let mut steps = 0;
+
+        let history = run_epochs(samples.len(), options, |index| {
+            let (sample, target) = (&samples[index], labels[index]);
+            let error = target - current.feed_forward(sample);
+            if error != 0.0 {
+                let (mut weights, mut bias) = (current.weights().clone(), current.bias());
+                update(&mut weights, &mut bias, sample, options.eta * error);
+                current.set_weights(weights);
+                current.set_bias(bias);
+            }
+
+            // Scoring takes a pass over every sample, so it only runs after the last
+            // sample of each epoch instead of after every update.
+            steps += 1;
+            if steps % samples.len() == 0 {
+                let errors = misclassified(&current, samples, labels);
+                if errors < best {
+                    best = errors;
+                    self.pocket = current.clone();
+                }
+            }
+            error != 0.0
+        });
+
+        self.misclassified = Some(best);
+        history
+    }
+}
+
+impl<const N: usize> fmt::Display for PocketPerceptron<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Pocket Perceptron:")?;
+        writeln!(f)?;
+        writeln!(f, "Inputs Size: {}", N)?;
+        writeln!(f, "Weights: {:?}", self.pocket.weights())?;
+        writeln!(f, "Bias: {}", self.pocket.bias())?;
+        if let Some(misclassified) = self.misclassified {
+            writeln!(f, "Misclassified: {}", misclassified)?;
+        }
+        writeln!(f)?;
+
+        Ok(())
+    }
+}
+
+/// The voted perceptron of Freund and Schapire. Training keeps every intermediate weight
+/// vector together with the number of samples it survived, and a prediction is their
+/// vote weighted by those counts.
+///
+/// [`averaged`](Self::averaged) collapses the vote into a single perceptron with the
+/// count-weighted mean of the weights, which predicts almost as well and much faster.
+/// Like the other learners in this module, it is `f64`-only.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VotedPerceptron<const N: usize> {
+    /// Each perceptron with the number of samples it classified before being updated.
+    perceptrons: Vec<(Perceptron<f64, N>, usize)>,
+}
+
+impl<const N: usize> Default for VotedPerceptron<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> VotedPerceptron<N> {
+    pub fn new() -> Self {
+        Self {
+            perceptrons: vec![(step_perceptron(), 0)],
+        }
+    }
+
+    /// Returns every perceptron seen during training with its vote count.
+    pub fn perceptrons(&self) -> &[(Perceptron<f64, N>, usize)] {
+        &self.perceptrons
+    }
+
+    /// Returns `1.0` if the count-weighted vote of the perceptrons is for class 1.
+    pub fn feed_forward(&self, inputs: &BVector<f64, N>) -> f64 {
+        let vote: f64 = self
+            .perceptrons
+            .iter()
+            .map(|(perceptron, count)| {
+                let sign = if perceptron.feed_forward(inputs) == 1.0 {
+                    1.0
+                } else {
+                    -1.0
+                };
+                sign * *count as f64
+            })
+            .sum();
+        if vote >= 0.0 {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the perceptron whose weights and bias are the count-weighted mean of every
+    /// perceptron seen during training.
+    pub fn averaged(&self) -> Perceptron<f64, N> {
+        let total: usize = self.perceptrons.iter().map(|(_, count)| count).sum();
+        let mut weights = BVector::from_element(0.0);
+        let mut bias = 0.0;
+        for (perceptron, count) in &self.perceptrons {
+            let share = *count as f64 / total.max(1) as f64;
+            weights += perceptron.weights().clone() * share;
+            bias += perceptron.bias() * share;
+        }
+
+        let mut averaged = step_perceptron();
+        averaged.set_weights(weights);
+        averaged.set_bias(bias);
+        averaged
+    }
+
+    /// Trains with the perceptron rule. Every update starts a new perceptron, and the
+    /// previous one keeps its count as its vote.
+    pub fn train(
+        &mut self,
+        samples: &[BVector<f64, N>],
+        labels: &[f64],
+        eta: f64,
+        epochs: usize,
+    ) -> TrainingHistory {
+        self.train_with(
+            samples,
+            labels,
+            &TrainOptions {
+                eta,
+                epochs,
+                ..TrainOptions::default()
+            },
+        )
+    }
+
+    /// Trains like [`train`](Self::train), with the given options.
+    pub fn train_with(
+        &mut self,
+        samples: &[BVector<f64, N>],
+        labels: &[f64],
+        options: &TrainOptions,
+    ) -> TrainingHistory {
+        check_labels(samples.len(), labels.len());
+
+        run_epochs(samples.len(), options, |index| {
+            let (sample, target) = (&samples[index], labels[index]);
+            let (current, count) = self
+                .perceptrons
+                .last_mut()
+                .expect("there is always a current perceptron");
+            let error = target - current.feed_forward(sample);
+            if error == 0.0 {
+                *count += 1;
+                return false;
+            }
+
+            let (mut weights, mut bias) = (current.weights().clone(), current.bias());
+            update(&mut weights, &mut bias, sample, options.eta * error);
+            let mut next = step_perceptron();
+            next.set_weights(weights);
+            next.set_bias(bias);
+            self.perceptrons.push((next, 1));
+            true
+        })
+    }
+}
+
+impl<const N: usize> fmt::Display for VotedPerceptron<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let averaged = self.averaged();
+        writeln!(f, "Voted Perceptron:")?;
+        writeln!(f)?;
+        writeln!(f, "Inputs Size: {}", N)?;
+        writeln!(f, "Perceptrons: {}", self.perceptrons.len())?;
+        writeln!(f, "Averaged Weights: {:?}", averaged.weights())?;
+        writeln!(f, "Averaged Bias: {}", averaged.bias())?;
+        writeln!(f)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bvector;
+
+    /// Separable except for the last sample, which sits among the class-0 samples.
+    fn noisy() -> (Vec<BVector<f64, 2>>, Vec<f64>) {
+        (
+            vec![
+                bvector![0.0, 0.0],
+                bvector![0.0, 1.0],
+                bvector![1.0, 0.0],
+                bvector![2.0, 2.0],
+                bvector![3.0, 2.0],
+                bvector![2.0, 3.0],
+                bvector![0.5, 0.5],
+            ],
+            vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0],
+        )
+    }
+
+    #[test]
+    fn adaline_converges_to_the_least_squares_fit() {
+        // The least-squares fit of y = x is w = 1, b = 0.
+        let samples = [bvector![0.0], bvector![1.0], bvector![2.0]];
+        let labels = [0.0, 1.0, 2.0];
+        let mut adaline = Adaline::<1>::new();
+
+        adaline.train_with(
+            &samples,
+            &labels,
+            &TrainOptions {
+                eta: 0.05,
+                epochs: 2000,
+                stop_on_convergence: false,
+                ..TrainOptions::default()
+            },
+        );
+
+        assert!((adaline.weights().get(0) - 1.0).abs() < 1e-6);
+        assert!(adaline.bias().abs() < 1e-6);
+    }
+
+    #[test]
+    fn pocket_keeps_the_best_weights_on_non_separable_data() {
+        let (samples, labels) = noisy();
+        let mut pocket = PocketPerceptron::<2>::new();
+
+        let history = pocket.train(&samples, &labels, 0.1, 50);
+
+        assert!(!history.converged);
+        assert_eq!(pocket.misclassified(), Some(1));
+        assert_eq!(misclassified(pocket.perceptron(), &samples, &labels), 1);
+        assert!(pocket.to_string().contains("Misclassified: 1"));
+    }
+
+    #[test]
+    fn voted_and_averaged_perceptrons_learn_separable_data() {
+        let (mut samples, mut labels) = noisy();
+        samples.pop();
+        labels.pop();
+        let mut voted = VotedPerceptron::<2>::new();
+
+        // Running on after convergence lets the final weights outvote the early ones.
+        let options = TrainOptions {
+            epochs: 50,
+            stop_on_convergence: false,
+            ..TrainOptions::default()
+        };
+        let history = voted.train_with(&samples, &labels, &options);
+        let averaged = voted.averaged();
+
+        assert!(history.converged);
+        assert!(voted.perceptrons().len() > 1);
+        for (sample, label) in samples.iter().zip(labels) {
+            assert_eq!(voted.feed_forward(sample), label);
+            assert_eq!(averaged.feed_forward(sample), label);
+        }
+    }
+}
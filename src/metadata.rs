@@ -0,0 +1,148 @@
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Describes what a model is and what its inputs and outputs mean. Attach it with
+/// [`NeuralNetwork::set_metadata`] and it is saved along with the network.
+///
+/// # Example
+///
+/// ```
+/// # use only_brain::{ModelMetadata, NeuralNetwork};
+/// let mut nn = NeuralNetwork::new(&vec![2, 1]);
+/// nn.set_metadata(ModelMetadata {
+///     feature_names: vec!["age".into(), "income".into()],
+///     output_labels: vec!["churn".into()],
+///     ..ModelMetadata::new("churn-v1")
+/// });
+///
+/// assert_eq!(nn.metadata().unwrap().name, "churn-v1");
+/// ```
+///
+/// [`NeuralNetwork::set_metadata`]: crate::NeuralNetwork::set_metadata
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ModelMetadata {
+    pub name: String,
+    pub description: String,
+    /// One name per input neuron, or empty if the inputs are not named.
+    pub feature_names: Vec<String>,
+    /// One label per output neuron, or empty if the outputs are not labelled. A network
+    /// with a single output is a binary classifier and may instead label both classes,
+    /// class 0 first.
+    pub output_labels: Vec<String>,
+    pub tags: BTreeMap<String, String>,
+    /// Seconds since the Unix epoch.
+    pub created_at: Option<u64>,
+    /// Version of this crate that created the metadata.
+    pub crate_version: String,
+}
+
+impl ModelMetadata {
+    /// Creates metadata with the given name, stamped with the crate version and, with the
+    /// `std` feature, the current time.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            created_at: now(),
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+fn now() -> Option<u64> {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .map(|elapsed| elapsed.as_secs())
+}
+
+#[cfg(not(feature = "std"))]
+fn now() -> Option<u64> {
+    None
+}
+
+impl fmt::Display for ModelMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Name: {}", self.name)?;
+        if !self.description.is_empty() {
+            writeln!(f, "Description: {}", self.description)?;
+        }
+        if !self.feature_names.is_empty() {
+            writeln!(f, "Features: {}", self.feature_names.join(", "))?;
+        }
+        if !self.output_labels.is_empty() {
+            writeln!(f, "Outputs: {}", self.output_labels.join(", "))?;
+        }
+        for (key, value) in &self.tags {
+            writeln!(f, "Tag {}: {}", key, value)?;
+        }
+        if let Some(created_at) = self.created_at {
+            writeln!(f, "Created At: {} (Unix time)", created_at)?;
+        }
+        writeln!(f, "Crate Version: {}", self.crate_version)
+    }
+}
+
+/// Error returned by [`NeuralNetwork::feed_forward_named`].
+///
+/// [`NeuralNetwork::feed_forward_named`]: crate::NeuralNetwork::feed_forward_named
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NamedInputError {
+    /// The network has no metadata with feature names.
+    NoFeatureNames,
+    MissingFeatures(Vec<String>),
+    UnknownFeatures(Vec<String>),
+}
+
+impl fmt::Display for NamedInputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NamedInputError::NoFeatureNames => write!(f, "the network has no feature names"),
+            NamedInputError::MissingFeatures(names) => {
+                write!(f, "missing features: {}", names.join(", "))
+            }
+            NamedInputError::UnknownFeatures(names) => {
+                write!(f, "unknown features: {}", names.join(", "))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NamedInputError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_stamps_the_crate_version_and_time() {
+        let metadata = ModelMetadata::new("m");
+
+        assert_eq!(metadata.crate_version, env!("CARGO_PKG_VERSION"));
+        #[cfg(feature = "std")]
+        assert!(metadata.created_at.unwrap() > 1_600_000_000);
+    }
+
+    #[test]
+    fn display_skips_empty_fields() {
+        let mut metadata = ModelMetadata {
+            feature_names: vec!["a".into(), "b".into()],
+            created_at: None,
+            ..ModelMetadata::new("m")
+        };
+        metadata.tags.insert("owner".into(), "risk".into());
+
+        let rendered = metadata.to_string();
+
+        assert!(rendered.contains("Features: a, b\n"));
+        assert!(rendered.contains("Tag owner: risk\n"));
+        assert!(!rendered.contains("Description"));
+        assert!(!rendered.contains("Outputs"));
+    }
+}
@@ -0,0 +1,774 @@
+use std::fmt;
+
+use crate::{Dataset, NeuralNetwork, Perceptron};
+
+/// Probabilities are clamped to `[EPSILON, 1 - EPSILON]` so log-loss stays finite.
+const LOG_LOSS_EPSILON: f64 = 1e-15;
+
+/// Counts of actual (rows) against predicted (columns) classes.
+///
+/// Precision, recall and F1 are 0 for classes that are never predicted or never occur.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfusionMatrix {
+    counts: Vec<Vec<usize>>,
+    labels: Vec<String>,
+}
+
+impl ConfusionMatrix {
+    /// Builds the matrix from the actual and predicted class of every sample. Classes are
+    /// labelled by their index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices have different lengths or a class is not below `num_classes`.
+    pub fn new(actual: &[usize], predicted: &[usize], num_classes: usize) -> Self {
+        assert_eq!(
+            actual.len(),
+            predicted.len(),
+            "got {} actual classes but {} predictions",
+            actual.len(),
+            predicted.len()
+        );
+        let mut counts = vec![vec![0; num_classes]; num_classes];
+        for (&a, &p) in actual.iter().zip(predicted) {
+            assert!(
+                a < num_classes && p < num_classes,
+                "class out of range for {} classes",
+                num_classes
+            );
+            counts[a][p] += 1;
+        }
+
+        Self {
+            counts,
+            labels: (0..num_classes).map(|class| class.to_string()).collect(),
+        }
+    }
+
+    /// Names the classes in the pretty-printed matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is not one label per class.
+    pub fn with_labels(mut self, labels: Vec<String>) -> Self {
+        assert_eq!(
+            labels.len(),
+            self.num_classes(),
+            "expected one label per class"
+        );
+        self.labels = labels;
+        self
+    }
+
+    pub fn num_classes(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Returns the number of samples of class `actual` predicted as class `predicted`.
+    pub fn count(&self, actual: usize, predicted: usize) -> usize {
+        self.counts[actual][predicted]
+    }
+
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.iter().flatten().sum()
+    }
+
+    fn true_positives(&self, class: usize) -> usize {
+        self.counts[class][class]
+    }
+
+    fn predicted(&self, class: usize) -> usize {
+        self.counts.iter().map(|row| row[class]).sum()
+    }
+
+    fn actual(&self, class: usize) -> usize {
+        self.counts[class].iter().sum()
+    }
+
+    pub fn accuracy(&self) -> f64 {
+        let correct: usize = (0..self.num_classes())
+            .map(|class| self.true_positives(class))
+            .sum();
+        ratio(correct, self.total())
+    }
+
+    pub fn precision(&self, class: usize) -> f64 {
+        ratio(self.true_positives(class), self.predicted(class))
+    }
+
+    pub fn recall(&self, class: usize) -> f64 {
+        ratio(self.true_positives(class), self.actual(class))
+    }
+
+    pub fn f1(&self, class: usize) -> f64 {
+        f1(self.precision(class), self.recall(class))
+    }
+
+    /// Unweighted mean of the per-class precision.
+    pub fn macro_precision(&self) -> f64 {
+        self.macro_average(Self::precision)
+    }
+
+    /// Unweighted mean of the per-class recall.
+    pub fn macro_recall(&self) -> f64 {
+        self.macro_average(Self::recall)
+    }
+
+    /// Unweighted mean of the per-class F1.
+    pub fn macro_f1(&self) -> f64 {
+        self.macro_average(Self::f1)
+    }
+
+    /// Precision over the pooled counts of every class. With one class per sample this
+    /// equals the accuracy.
+    pub fn micro_precision(&self) -> f64 {
+        let true_positives: usize = (0..self.num_classes())
+            .map(|class| self.true_positives(class))
+            .sum();
+        let predicted: usize = (0..self.num_classes())
+            .map(|class| self.predicted(class))
+            .sum();
+        ratio(true_positives, predicted)
+    }
+
+    /// Recall over the pooled counts of every class. With one class per sample this
+    /// equals the accuracy.
+    pub fn micro_recall(&self) -> f64 {
+        let true_positives: usize = (0..self.num_classes())
+            .map(|class| self.true_positives(class))
+            .sum();
+        let actual: usize = (0..self.num_classes())
+            .map(|class| self.actual(class))
+            .sum();
+        ratio(true_positives, actual)
+    }
+
+    pub fn micro_f1(&self) -> f64 {
+        f1(self.micro_precision(), self.micro_recall())
+    }
+
+    fn macro_average(&self, metric: fn(&Self, usize) -> f64) -> f64 {
+        let sum: f64 = (0..self.num_classes())
+            .map(|class| metric(self, class))
+            .sum();
+        sum / self.num_classes() as f64
+    }
+}
+
+impl fmt::Display for ConfusionMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let corner = "actual \\ predicted";
+        let label_width = self
+            .labels
+            .iter()
+            .map(String::len)
+            .max()
+            .unwrap_or(0)
+            .max(corner.len());
+        let widths: Vec<usize> = (0..self.num_classes())
+            .map(|class| {
+                let widest_count = self
+                    .counts
+                    .iter()
+                    .map(|row| row[class].to_string().len())
+                    .max();
+                self.labels[class].len().max(widest_count.unwrap_or(0))
+            })
+            .collect();
+
+        write!(f, "{:<label_width$}", corner)?;
+        for (label, width) in self.labels.iter().zip(&widths) {
+            write!(f, "  {:>width$}", label)?;
+        }
+        writeln!(f)?;
+
+        for (label, row) in self.labels.iter().zip(&self.counts) {
+            write!(f, "{:<label_width$}", label)?;
+            for (count, width) in row.iter().zip(&widths) {
+                write!(f, "  {:>width$}", count)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Classification metrics of a model over a labelled dataset.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClassificationReport {
+    pub confusion_matrix: ConfusionMatrix,
+    pub accuracy: f64,
+    pub macro_precision: f64,
+    pub macro_recall: f64,
+    pub macro_f1: f64,
+    pub micro_precision: f64,
+    pub micro_recall: f64,
+    pub micro_f1: f64,
+    /// Only for binary classification, and only if both classes occur.
+    pub roc_auc: Option<f64>,
+    /// Only for binary classification.
+    pub log_loss: Option<f64>,
+}
+
+impl ClassificationReport {
+    /// Builds the report from the actual class and the predicted class probabilities of
+    /// every sample.
+    fn from_probabilities(
+        actual: &[usize],
+        probabilities: &[Vec<f64>],
+        num_classes: usize,
+        labels: Option<Vec<String>>,
+    ) -> Self {
+        let predicted: Vec<usize> = probabilities.iter().map(|row| argmax(row)).collect();
+        let mut confusion_matrix = ConfusionMatrix::new(actual, &predicted, num_classes);
+        if let Some(labels) = labels {
+            confusion_matrix = confusion_matrix.with_labels(labels);
+        }
+
+        let (roc_auc, log_loss) = if num_classes == 2 {
+            let scores: Vec<f64> = probabilities.iter().map(|row| row[1]).collect();
+            (
+                roc_auc(actual, &scores),
+                Some(self::log_loss(actual, &scores)),
+            )
+        } else {
+            (None, None)
+        };
+
+        Self {
+            accuracy: confusion_matrix.accuracy(),
+            macro_precision: confusion_matrix.macro_precision(),
+            macro_recall: confusion_matrix.macro_recall(),
+            macro_f1: confusion_matrix.macro_f1(),
+            micro_precision: confusion_matrix.micro_precision(),
+            micro_recall: confusion_matrix.micro_recall(),
+            micro_f1: confusion_matrix.micro_f1(),
+            confusion_matrix,
+            roc_auc,
+            log_loss,
+        }
+    }
+}
+
+impl fmt::Display for ClassificationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Accuracy: {:.4}", self.accuracy)?;
+        writeln!(
+            f,
+            "Macro Precision: {:.4}  Recall: {:.4}  F1: {:.4}",
+            self.macro_precision, self.macro_recall, self.macro_f1
+        )?;
+        writeln!(
+            f,
+            "Micro Precision: {:.4}  Recall: {:.4}  F1: {:.4}",
+            self.micro_precision, self.micro_recall, self.micro_f1
+        )?;
+        if let Some(roc_auc) = self.roc_auc {
+            writeln!(f, "ROC AUC: {:.4}", roc_auc)?;
+        }
+        if let Some(log_loss) = self.log_loss {
+            writeln!(f, "Log-Loss: {:.4}", log_loss)?;
+        }
+        writeln!(f)?;
+        write!(f, "{}", self.confusion_matrix)
+    }
+}
+
+/// Regression metrics of a model over a labelled dataset.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RegressionReport {
+    pub mse: f64,
+    pub mae: f64,
+    pub r2: f64,
+}
+
+impl RegressionReport {
+    fn new(targets: &[Vec<f64>], predictions: &[Vec<f64>]) -> Self {
+        Self {
+            mse: mean_squared_error(targets, predictions),
+            mae: mean_absolute_error(targets, predictions),
+            r2: r2_score(targets, predictions),
+        }
+    }
+}
+
+impl fmt::Display for RegressionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "MSE: {:.4}", self.mse)?;
+        writeln!(f, "MAE: {:.4}", self.mae)?;
+        writeln!(f, "R²: {:.4}", self.r2)
+    }
+}
+
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+fn f1(precision: f64, recall: f64) -> f64 {
+    if precision + recall == 0.0 {
+        0.0
+    } else {
+        2.0 * precision * recall / (precision + recall)
+    }
+}
+
+fn argmax(values: &[f64]) -> usize {
+    values
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map_or(0, |(index, _)| index)
+}
+
+/// Reads every target row as one of `num_classes` classes: a single column holds the
+/// class index, `num_classes` columns are one-hot encoded.
+///
+/// # Panics
+///
+/// Panics if the dataset is empty or a target is not one of the classes.
+fn target_classes(targets: &[Vec<f64>], num_classes: usize) -> Vec<usize> {
+    assert!(
+        !targets.is_empty(),
+        "cannot evaluate a model on an empty dataset"
+    );
+    targets
+        .iter()
+        .enumerate()
+        .map(|(sample, target)| match target[..] {
+            [class] => {
+                let class = class.round();
+                assert!(
+                    class >= 0.0 && class < num_classes as f64,
+                    "sample {} has target class {}, expected a class index below {}",
+                    sample,
+                    class,
+                    num_classes
+                );
+                class as usize
+            }
+            _ => {
+                assert_eq!(
+                    target.len(),
+                    num_classes,
+                    "sample {} has a one-hot target of {} columns, expected {}",
+                    sample,
+                    target.len(),
+                    num_classes
+                );
+                argmax(target)
+            }
+        })
+        .collect()
+}
+
+/// Fraction of samples whose predicted class is the actual class.
+pub fn accuracy(actual: &[usize], predicted: &[usize]) -> f64 {
+    let num_classes = actual
+        .iter()
+        .chain(predicted)
+        .max()
+        .map_or(0, |&max| max + 1);
+    ConfusionMatrix::new(actual, predicted, num_classes).accuracy()
+}
+
+/// Area under the ROC curve of `scores` for the positive class 1, computed from the ranks
+/// of the scores. Returns `None` unless both classes occur.
+pub fn roc_auc(actual: &[usize], scores: &[f64]) -> Option<f64> {
+    assert_eq!(actual.len(), scores.len(), "expected one score per sample");
+    let positives = actual.iter().filter(|&&class| class == 1).count();
+    let negatives = actual.len() - positives;
+    if positives == 0 || negatives == 0 {
+        return None;
+    }
+
+    let mut order: Vec<usize> = (0..scores.len()).collect();
+    order.sort_by(|&a, &b| scores[a].total_cmp(&scores[b]));
+
+    // Tied scores share the mean of their ranks.
+    let mut positive_rank_sum = 0.0;
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start;
+        while end + 1 < order.len() && scores[order[end + 1]] == scores[order[start]] {
+            end += 1;
+        }
+        let mean_rank = (start + end) as f64 / 2.0 + 1.0;
+        let tied_positives = order[start..=end]
+            .iter()
+            .filter(|&&i| actual[i] == 1)
+            .count();
+        positive_rank_sum += mean_rank * tied_positives as f64;
+        start = end + 1;
+    }
+
+    let positives = positives as f64;
+    Some((positive_rank_sum - positives * (positives + 1.0) / 2.0) / (positives * negatives as f64))
+}
+
+/// Mean binary cross-entropy of the predicted probabilities of class 1.
+pub fn log_loss(actual: &[usize], probabilities: &[f64]) -> f64 {
+    assert_eq!(
+        actual.len(),
+        probabilities.len(),
+        "expected one probability per sample"
+    );
+    let total: f64 = actual
+        .iter()
+        .zip(probabilities)
+        .map(|(&class, &p)| {
+            let p = p.clamp(LOG_LOSS_EPSILON, 1.0 - LOG_LOSS_EPSILON);
+            if class == 1 {
+                -p.ln()
+            } else {
+                -(1.0 - p).ln()
+            }
+        })
+        .sum();
+    total / actual.len() as f64
+}
+
+/// Returns `(target, prediction)` pairs over every output of every sample.
+fn pairs<'a>(
+    targets: &'a [Vec<f64>],
+    predictions: &'a [Vec<f64>],
+) -> impl Iterator<Item = (f64, f64)> + 'a {
+    assert_eq!(
+        targets.len(),
+        predictions.len(),
+        "expected one prediction per target"
+    );
+    targets
+        .iter()
+        .zip(predictions)
+        .flat_map(|(target, prediction)| target.iter().copied().zip(prediction.iter().copied()))
+}
+
+/// Mean squared error over every output of every sample.
+pub fn mean_squared_error(targets: &[Vec<f64>], predictions: &[Vec<f64>]) -> f64 {
+    let (sum, count) = pairs(targets, predictions).fold((0.0, 0), |(sum, count), (t, p)| {
+        (sum + (t - p) * (t - p), count + 1)
+    });
+    sum / count as f64
+}
+
+/// Mean absolute error over every output of every sample.
+pub fn mean_absolute_error(targets: &[Vec<f64>], predictions: &[Vec<f64>]) -> f64 {
+    let (sum, count) = pairs(targets, predictions).fold((0.0, 0), |(sum, count), (t, p)| {
+        (sum + (t - p).abs(), count + 1)
+    });
+    sum / count as f64
+}
+
+/// Coefficient of determination, averaged over the outputs.
+///
+/// The score is undefined for an output whose targets are all the same, so such an output
+/// scores 1.0 when it is predicted exactly and 0.0 otherwise, like a constant model.
+///
+/// # Panics
+///
+/// Panics if there are no targets or not one prediction per target.
+pub fn r2_score(targets: &[Vec<f64>], predictions: &[Vec<f64>]) -> f64 {
+    assert!(!targets.is_empty(), "cannot score an empty set of targets");
+    assert_eq!(
+        targets.len(),
+        predictions.len(),
+        "expected one prediction per target"
+    );
+    let outputs = targets.first().map_or(0, Vec::len);
+    let n = targets.len() as f64;
+
+    let sum: f64 = (0..outputs)
+        .map(|output| {
+            let mean = targets.iter().map(|row| row[output]).sum::<f64>() / n;
+            let residual: f64 = targets
+                .iter()
+                .zip(predictions)
+                .map(|(t, p)| (t[output] - p[output]).powi(2))
+                .sum();
+            let total: f64 = targets.iter().map(|t| (t[output] - mean).powi(2)).sum();
+            match (total, residual) {
+                (0.0, 0.0) => 1.0,
+                (0.0, _) => 0.0,
+                _ => 1.0 - residual / total,
+            }
+        })
+        .sum();
+    sum / outputs as f64
+}
+
+impl NeuralNetwork {
+    /// Classifies every sample of `dataset` and compares the result with its targets.
+    ///
+    /// A target row with one column holds the class index, one with several columns is
+    /// read as one-hot. Predictions come from [`predict_proba_many`](Self::predict_proba_many),
+    /// so a single-output network has two classes. The confusion matrix uses the class
+    /// labels from the network metadata when it labels every class.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dataset is empty, if a class index is negative or not below the
+    /// number of classes, or if a one-hot target does not have one column per class.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use only_brain::{Dataset, NeuralNetwork};
+    /// let nn = NeuralNetwork::new(&vec![2, 4, 1]);
+    /// let dataset = Dataset::new(
+    ///     vec![vec![0.0, 0.0], vec![0.0, 1.0], vec![1.0, 0.0], vec![1.0, 1.0]],
+    ///     vec![vec![0.0], vec![1.0], vec![1.0], vec![0.0]],
+    /// );
+    ///
+    /// let report = nn.evaluate_classification(&dataset);
+    /// println!("{}", report);
+    /// assert_eq!(report.confusion_matrix.total(), 4);
+    /// ```
+    pub fn evaluate_classification(&self, dataset: &Dataset) -> ClassificationReport {
+        let num_classes = self.layer_size(self.num_layers() - 1).max(2);
+        let actual = target_classes(dataset.targets(), num_classes);
+        let probabilities = self.predict_proba_many(dataset.inputs());
+        let labels = (0..num_classes)
+            .map(|class| self.class_label(num_classes, class).map(str::to_string))
+            .collect();
+
+        ClassificationReport::from_probabilities(&actual, &probabilities, num_classes, labels)
+    }
+
+    /// Runs the network over every sample of `dataset` and compares the outputs with its
+    /// targets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dataset is empty.
+    pub fn evaluate_regression(&self, dataset: &Dataset) -> RegressionReport {
+        assert!(
+            !dataset.is_empty(),
+            "cannot evaluate a model on an empty dataset"
+        );
+        RegressionReport::new(dataset.targets(), &self.feed_forward_many(dataset.inputs()))
+    }
+}
+
+impl<const N: usize> Perceptron<f64, N> {
+    fn outputs(&self, dataset: &Dataset) -> Vec<f64> {
+        dataset
+            .inputs()
+            .iter()
+            .map(|input| {
+                let input: [f64; N] = input
+                    .as_slice()
+                    .try_into()
+                    .unwrap_or_else(|_| panic!("expected {} inputs, got {}", N, input.len()));
+                self.feed_forward(&crate::BVector::from_array(input))
+            })
+            .collect()
+    }
+
+    /// Classifies every sample of `dataset` as class 1 when the output is at least 0.5,
+    /// and compares the result with its targets, which hold the class index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dataset is empty or a target is not class 0 or 1.
+    pub fn evaluate_classification(&self, dataset: &Dataset) -> ClassificationReport {
+        let actual = target_classes(dataset.targets(), 2);
+        let probabilities: Vec<Vec<f64>> = self
+            .outputs(dataset)
+            .into_iter()
+            .map(|output| {
+                let p = output.clamp(0.0, 1.0);
+                vec![1.0 - p, p]
+            })
+            .collect();
+
+        ClassificationReport::from_probabilities(&actual, &probabilities, 2, None)
+    }
+
+    /// Runs the perceptron over every sample of `dataset` and compares the outputs with
+    /// its targets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dataset is empty.
+    pub fn evaluate_regression(&self, dataset: &Dataset) -> RegressionReport {
+        assert!(
+            !dataset.is_empty(),
+            "cannot evaluate a model on an empty dataset"
+        );
+        let predictions: Vec<Vec<f64>> = self
+            .outputs(dataset)
+            .into_iter()
+            .map(|output| vec![output])
+            .collect();
+        RegressionReport::new(dataset.targets(), &predictions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bvector, ActivationFunction, ModelMetadata};
+    use nalgebra::{dmatrix, dvector};
+
+    const EPSILON: f64 = 1e-12;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < EPSILON,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn confusion_matrix_metrics() {
+        // Class 0: 2 right, 1 predicted as 1. Class 1: 1 right. Class 2: 1 predicted as 1.
+        let matrix = ConfusionMatrix::new(&[0, 0, 0, 1, 2], &[0, 0, 1, 1, 1], 3);
+
+        assert_eq!(matrix.count(0, 1), 1);
+        assert_close(matrix.accuracy(), 0.6);
+        assert_close(matrix.precision(1), 1.0 / 3.0);
+        assert_close(matrix.recall(0), 2.0 / 3.0);
+        assert_close(matrix.precision(2), 0.0);
+        assert_close(matrix.macro_precision(), (1.0 + 1.0 / 3.0) / 3.0);
+        assert_close(matrix.macro_recall(), (2.0 / 3.0 + 1.0) / 3.0);
+        assert_close(matrix.micro_f1(), matrix.accuracy());
+    }
+
+    #[test]
+    fn confusion_matrix_pretty_prints_aligned_columns() {
+        let matrix = ConfusionMatrix::new(&[0, 1, 1], &[0, 1, 0], 2)
+            .with_labels(vec!["no".into(), "yes".into()]);
+
+        assert_eq!(
+            matrix.to_string(),
+            "actual \\ predicted  no  yes\n\
+             no                   1    0\n\
+             yes                  1    1\n"
+        );
+    }
+
+    #[test]
+    fn roc_auc_handles_ties_and_single_class_input() {
+        assert_close(
+            roc_auc(&[0, 0, 1, 1], &[0.1, 0.4, 0.35, 0.8]).unwrap(),
+            0.75,
+        );
+        assert_close(roc_auc(&[0, 1], &[0.5, 0.5]).unwrap(), 0.5);
+        assert_eq!(roc_auc(&[1, 1], &[0.2, 0.9]), None);
+    }
+
+    #[test]
+    fn log_loss_matches_the_cross_entropy() {
+        let expected = -(0.9f64.ln() + 0.8f64.ln()) / 2.0;
+
+        assert_close(log_loss(&[1, 0], &[0.9, 0.2]), expected);
+        assert!(log_loss(&[1], &[0.0]).is_finite());
+    }
+
+    #[test]
+    fn regression_metrics() {
+        let targets = vec![vec![1.0], vec![2.0], vec![3.0]];
+        let predictions = vec![vec![1.0], vec![2.5], vec![2.0]];
+
+        assert_close(mean_squared_error(&targets, &predictions), 1.25 / 3.0);
+        assert_close(mean_absolute_error(&targets, &predictions), 0.5);
+        assert_close(r2_score(&targets, &predictions), 1.0 - 1.25 / 2.0);
+        assert_close(r2_score(&targets, &targets), 1.0);
+
+        let constant = vec![vec![2.0], vec![2.0]];
+        assert_close(r2_score(&constant, &constant), 1.0);
+        assert_close(r2_score(&constant, &[vec![2.0], vec![3.0]]), 0.0);
+    }
+
+    #[test]
+    fn networks_are_evaluated_against_one_hot_targets() {
+        let mut nn = NeuralNetwork::new(&vec![2, 2]);
+        nn.set_activation_function(ActivationFunction::ReLU);
+        nn.set_layer_weights(1, dmatrix![1.0, 0.0; 0.0, 1.0]);
+        nn.set_layer_biases(1, dvector![0.0, 0.0]);
+        nn.set_metadata(ModelMetadata {
+            output_labels: vec!["cat".into(), "dog".into()],
+            ..ModelMetadata::new("pets")
+        });
+        let dataset = Dataset::new(
+            vec![vec![0.9, 0.1], vec![0.2, 0.8], vec![0.6, 0.4]],
+            vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![0.0, 1.0]],
+        );
+
+        let report = nn.evaluate_classification(&dataset);
+
+        assert_close(report.accuracy, 2.0 / 3.0);
+        assert_eq!(report.confusion_matrix.labels(), &["cat", "dog"]);
+        assert_close(report.roc_auc.unwrap(), 1.0);
+        assert!(report.to_string().contains("Accuracy: 0.6667"));
+    }
+
+    #[test]
+    fn perceptrons_are_evaluated_on_class_indices() {
+        let mut perceptron = Perceptron::<f64, 2>::new(ActivationFunction::BinaryStep);
+        perceptron.set_weights(bvector![1.0, -1.0]);
+        let dataset = Dataset::new(
+            vec![
+                vec![1.0, 0.0],
+                vec![0.0, 1.0],
+                vec![2.0, 1.0],
+                vec![0.0, 3.0],
+            ],
+            vec![vec![1.0], vec![0.0], vec![0.0], vec![0.0]],
+        );
+
+        let report = perceptron.evaluate_classification(&dataset);
+
+        assert_close(report.accuracy, 0.75);
+        assert_eq!(report.confusion_matrix.count(0, 1), 1);
+        assert_close(perceptron.evaluate_regression(&dataset).mae, 0.25);
+    }
+
+    #[test]
+    fn binary_reports_use_the_labels_of_a_single_output_network() {
+        let mut nn = NeuralNetwork::new(&vec![1, 1]);
+        nn.set_activation_function(ActivationFunction::ReLU);
+        nn.set_layer_weights(1, dmatrix![1.0]);
+        nn.set_layer_biases(1, dvector![0.0]);
+        nn.set_metadata(ModelMetadata {
+            output_labels: vec!["ham".into(), "spam".into()],
+            ..ModelMetadata::new("spam")
+        });
+        let dataset = Dataset::new(vec![vec![0.9], vec![0.2]], vec![vec![1.0], vec![0.0]]);
+
+        let report = nn.evaluate_classification(&dataset);
+
+        assert_eq!(report.confusion_matrix.labels(), &["ham", "spam"]);
+        assert_close(report.accuracy, 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "sample 1 has target class 2, expected a class index below 2")]
+    fn out_of_range_targets_are_reported() {
+        let nn = NeuralNetwork::new(&vec![1, 1]);
+        nn.evaluate_classification(&Dataset::new(
+            vec![vec![0.5], vec![0.5]],
+            vec![vec![0.0], vec![2.0]],
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot evaluate a model on an empty dataset")]
+    fn empty_datasets_are_rejected() {
+        NeuralNetwork::new(&vec![1, 1]).evaluate_regression(&Dataset::new(Vec::new(), Vec::new()));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot score an empty set of targets")]
+    fn r2_score_rejects_empty_targets() {
+        r2_score(&[], &[]);
+    }
+}
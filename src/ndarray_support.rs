@@ -0,0 +1,119 @@
+use nalgebra::{DMatrix, DVector};
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2};
+
+use crate::convert::{IntoBiasVector, IntoWeightsMatrix};
+use crate::NeuralNetwork;
+
+fn to_dmatrix(array: ArrayView2<f64>) -> DMatrix<f64> {
+    let (rows, cols) = array.dim();
+    DMatrix::from_fn(rows, cols, |row, col| array[[row, col]])
+}
+
+fn to_array2(matrix: &DMatrix<f64>) -> Array2<f64> {
+    Array2::from_shape_fn(matrix.shape(), |(row, col)| matrix[(row, col)])
+}
+
+impl IntoWeightsMatrix for Array2<f64> {
+    fn into_weights_matrix(self) -> DMatrix<f64> {
+        to_dmatrix(self.view())
+    }
+}
+
+impl IntoWeightsMatrix for ArrayView2<'_, f64> {
+    fn into_weights_matrix(self) -> DMatrix<f64> {
+        to_dmatrix(self)
+    }
+}
+
+impl IntoBiasVector for Array1<f64> {
+    fn into_bias_vector(self) -> DVector<f64> {
+        DVector::from_iterator(self.len(), self)
+    }
+}
+
+impl IntoBiasVector for ArrayView1<'_, f64> {
+    fn into_bias_vector(self) -> DVector<f64> {
+        DVector::from_iterator(self.len(), self.iter().copied())
+    }
+}
+
+impl NeuralNetwork {
+    /// Feeds a single sample to the network, like [`feed_forward`](Self::feed_forward).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use only_brain::NeuralNetwork;
+    /// use ndarray::array;
+    ///
+    /// let nn = NeuralNetwork::new(&vec![2, 1]);
+    /// let output = nn.feed_forward_array(array![0.5, 0.2].view());
+    ///
+    /// assert_eq!(output.to_vec(), nn.feed_forward(&vec![0.5, 0.2]));
+    /// ```
+    pub fn feed_forward_array(&self, input: ArrayView1<f64>) -> Array1<f64> {
+        Array1::from(self.feed_forward(&input.to_vec()))
+    }
+
+    /// Feeds a batch of samples, one per row of `inputs`, and returns one row of outputs
+    /// per sample, like [`feed_forward_batch`](Self::feed_forward_batch).
+    pub fn feed_forward_array2(&self, inputs: ArrayView2<f64>) -> Array2<f64> {
+        to_array2(&self.feed_forward_batch(&to_dmatrix(inputs)))
+    }
+
+    /// Returns a copy of the weights matrix of the given layer as an ndarray.
+    pub fn layer_weights_array(&self, layer: usize) -> Array2<f64> {
+        to_array2(self.layer_weights(layer))
+    }
+
+    /// Returns a copy of the biases of the given layer as an ndarray.
+    pub fn layer_biases_array(&self, layer: usize) -> Array1<f64> {
+        self.layer_biases(layer).iter().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{dmatrix, dvector};
+    use ndarray::array;
+
+    #[test]
+    fn weights_and_biases_can_be_set_from_arrays() {
+        let mut nn = NeuralNetwork::new(&vec![2, 2]);
+        nn.set_layer_weights(1, array![[0.1, 0.2], [0.3, 0.4]]);
+        nn.set_layer_biases(1, array![0.5, 0.6].view());
+
+        assert_eq!(nn.layer_weights(1), &dmatrix![0.1, 0.2; 0.3, 0.4]);
+        assert_eq!(nn.layer_biases(1), &dvector![0.5, 0.6]);
+        assert_eq!(nn.layer_weights_array(1), array![[0.1, 0.2], [0.3, 0.4]]);
+        assert_eq!(nn.layer_biases_array(1), array![0.5, 0.6]);
+    }
+
+    #[test]
+    fn batched_array_inference_matches_feed_forward() {
+        let nn = NeuralNetwork::new(&vec![2, 3, 2]);
+        let inputs = array![[0.5, 0.2], [0.1, 0.9], [-1.0, 0.0]];
+
+        let outputs = nn.feed_forward_array2(inputs.view());
+
+        assert_eq!(outputs.dim(), (3, 2));
+        for (row, sample) in inputs.rows().into_iter().enumerate() {
+            let expected = nn.feed_forward(&sample.to_vec());
+            for (col, value) in expected.iter().enumerate() {
+                assert!((outputs[[row, col]] - value).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn non_contiguous_views_are_supported() {
+        let nn = NeuralNetwork::new(&vec![2, 1]);
+        let wide = array![[0.5, 9.0, 0.2], [0.1, 9.0, 0.9]];
+        let columns = wide.slice(ndarray::s![.., ..;2]);
+
+        let outputs = nn.feed_forward_array2(columns);
+
+        assert_eq!(outputs[[1, 0]], nn.feed_forward(&vec![0.1, 0.9])[0]);
+    }
+}
@@ -1,8 +1,16 @@
 use crate::activation_functions::{get_activation_function, ActivationFunction};
+use crate::bvector::BVector;
+use crate::convert::{IntoBiasVector, IntoWeightsMatrix};
 use crate::layer::Layer;
+use crate::metadata::ModelMetadata;
+#[cfg(feature = "std")]
+use crate::metadata::NamedInputError;
+use crate::preprocessing::Scaler;
+use alloc::vec::Vec;
+use core::fmt;
 use nalgebra::{DMatrix, DVector};
-use rand::rng;
-use std::fmt;
+use rand::Rng;
+#[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
 /// Neural Network
@@ -34,10 +42,34 @@ use serde::{Deserialize, Serialize};
 ///     println!("{:?}", output);
 /// }
 /// ```
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct NeuralNetwork {
     layers: Vec<Layer>,
     activation_function: Option<ActivationFunction>,
+    input_transform: Option<Scaler>,
+    metadata: Option<ModelMetadata>,
+}
+
+/// The layout [`dump_model`](crate::dump_model) wrote before model files had a format
+/// header, from before networks carried an input transform and metadata.
+#[cfg(feature = "std")]
+#[derive(Deserialize)]
+pub(crate) struct LegacyNeuralNetwork {
+    layers: Vec<Layer>,
+    activation_function: Option<ActivationFunction>,
+}
+
+#[cfg(feature = "std")]
+impl From<LegacyNeuralNetwork> for NeuralNetwork {
+    fn from(legacy: LegacyNeuralNetwork) -> Self {
+        Self {
+            layers: legacy.layers,
+            activation_function: legacy.activation_function,
+            input_transform: None,
+            metadata: None,
+        }
+    }
 }
 
 impl NeuralNetwork {
@@ -55,7 +87,20 @@ impl NeuralNetwork {
     /// # use only_brain::NeuralNetwork;
     /// let nn = NeuralNetwork::new(&vec![2, 2, 1]);
     /// ```
+    #[cfg(feature = "std")]
+    #[allow(clippy::ptr_arg)] // Public signature, kept for compatibility.
     pub fn new(layers: &Vec<usize>) -> Self {
+        Self::new_with_rng(layers, &mut rand::rng())
+    }
+
+    /// Creates a new Neural Network like [`new`](Self::new), drawing the initial weights
+    /// from `rng`. This is the constructor to use without the `std` feature, or when the
+    /// initial weights must be reproducible.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`new`](Self::new).
+    pub fn new_with_rng<R: Rng>(layers: &[usize], rng: &mut R) -> Self {
         assert!(
             layers.len() >= 2,
             "a neural network needs at least an input and an output layer, got {}",
@@ -66,22 +111,33 @@ impl NeuralNetwork {
             "every layer must have at least one neuron, got {layers:?}"
         );
 
-        let mut rng = rng();
-
         let layers = layers
             .iter()
             .zip(layers.iter().skip(1))
-            .map(|(a, b)| Layer::from_size(*b, *a, &mut rng))
+            .map(|(a, b)| Layer::from_size(*b, *a, rng))
             .collect::<Vec<Layer>>();
 
         Self {
             layers,
             activation_function: None,
+            input_transform: None,
+            metadata: None,
+        }
+    }
+
+    /// Builds a network from layers whose sizes are already known to chain.
+    pub(crate) fn from_layers(layers: Vec<Layer>, activation_function: ActivationFunction) -> Self {
+        Self {
+            layers,
+            activation_function: Some(activation_function),
+            input_transform: None,
+            metadata: None,
         }
     }
 
     /// Feeds the given inputs to the neural network and returns the output. The inputs
-    /// vector must have the same size as the first layer of the network.
+    /// vector must have the same size as the first layer of the network. If the network
+    /// has an [input transform](Self::set_input_transform), the inputs are scaled first.
     ///
     /// # Example
     ///
@@ -101,7 +157,10 @@ impl NeuralNetwork {
     /// # }
     /// ```
     pub fn feed_forward(&self, inputs: &Vec<f64>) -> Vec<f64> {
-        let mut outputs = DVector::from(Vec::clone(inputs));
+        let mut outputs = DVector::from(match &self.input_transform {
+            Some(scaler) => scaler.transform(inputs),
+            None => Vec::clone(inputs),
+        });
         let activation = get_activation_function(self.activation_function());
 
         for layer in &self.layers {
@@ -111,24 +170,157 @@ impl NeuralNetwork {
         outputs.data.into()
     }
 
+    /// Feeds a single sample held in a [`BVector`], like [`feed_forward`](Self::feed_forward).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is not the size of the input layer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use only_brain::{bvector, NeuralNetwork};
+    /// let nn = NeuralNetwork::new(&vec![2, 1]);
+    ///
+    /// assert_eq!(nn.feed_forward_bvector(&bvector![0.5, 0.2]), nn.feed_forward(&vec![0.5, 0.2]));
+    /// ```
+    pub fn feed_forward_bvector<const N: usize>(&self, inputs: &BVector<f64, N>) -> Vec<f64> {
+        assert_eq!(
+            N,
+            self.input_layer_size(),
+            "expected {} inputs, got {}",
+            self.input_layer_size(),
+            N
+        );
+        self.feed_forward(&inputs.clone().into())
+    }
+
+    /// Feeds every sample in `inputs` to the network and returns the outputs in the same
+    /// order.
+    ///
+    /// With the `parallel` feature the samples are spread over the rayon thread pool.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use only_brain::NeuralNetwork;
+    /// let nn = NeuralNetwork::new(&vec![2, 3, 1]);
+    /// let samples = vec![vec![0.5, 0.2], vec![0.1, 0.9]];
+    ///
+    /// let outputs = nn.feed_forward_many(&samples);
+    /// assert_eq!(outputs[1], nn.feed_forward(&samples[1]));
+    /// ```
+    pub fn feed_forward_many(&self, inputs: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            inputs
+                .par_iter()
+                .map(|sample| self.feed_forward(sample))
+                .collect()
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            inputs
+                .iter()
+                .map(|sample| self.feed_forward(sample))
+                .collect()
+        }
+    }
+
+    /// Feeds a batch of samples, one per row of `inputs`, and returns one row of outputs
+    /// per sample. Each layer runs as a single matrix-matrix product.
+    ///
+    /// With the `parallel` feature the rows are split into chunks that are forwarded on
+    /// the rayon thread pool and stitched back together in their original order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `inputs` does not have one column per input neuron.
+    pub fn feed_forward_batch(&self, inputs: &DMatrix<f64>) -> DMatrix<f64> {
+        assert_eq!(
+            inputs.ncols(),
+            self.input_layer_size(),
+            "expected {} input columns, got {}",
+            self.input_layer_size(),
+            inputs.ncols()
+        );
+
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+
+            const CHUNK_ROWS: usize = 256;
+            let starts: Vec<usize> = (0..inputs.nrows()).step_by(CHUNK_ROWS).collect();
+            let chunks: Vec<DMatrix<f64>> = starts
+                .par_iter()
+                .map(|&start| {
+                    let rows = CHUNK_ROWS.min(inputs.nrows() - start);
+                    self.forward_rows(inputs.rows(start, rows).into_owned())
+                })
+                .collect();
+
+            let mut outputs =
+                DMatrix::zeros(inputs.nrows(), self.layer_size(self.num_layers() - 1));
+            for (start, chunk) in starts.into_iter().zip(chunks) {
+                outputs.rows_mut(start, chunk.nrows()).copy_from(&chunk);
+            }
+            outputs
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            self.forward_rows(inputs.clone())
+        }
+    }
+
+    fn forward_rows(&self, mut outputs: DMatrix<f64>) -> DMatrix<f64> {
+        if let Some(scaler) = &self.input_transform {
+            scaler.transform_rows(&mut outputs);
+        }
+        let activation = get_activation_function(self.activation_function());
+        for layer in &self.layers {
+            outputs = layer.forward_batch(&outputs, activation);
+        }
+        outputs
+    }
+
     /// Sets the layer weights for the given layer. The weights matrix must have the size
     /// of the layer neurons x layer inputs. The layer index must be greater than 0 since it
     /// corresponds to the layer number that receives these weights.
-    pub fn set_layer_weights(&mut self, layer: usize, weights: DMatrix<f64>) {
+    pub fn set_layer_weights(&mut self, layer: usize, weights: impl IntoWeightsMatrix) {
         if layer == 0 {
             panic!("Invalid layer index");
         }
-        self.layers[layer - 1].set_weights(weights);
+        self.layers[layer - 1].set_weights(weights.into_weights_matrix());
+    }
+
+    /// Returns the weights matrix of the given layer, shaped layer neurons x layer
+    /// inputs. The layer index must be greater than 0 since the input layer does not have
+    /// weights.
+    pub fn layer_weights(&self, layer: usize) -> &DMatrix<f64> {
+        if layer == 0 {
+            panic!("Invalid layer index");
+        }
+        self.layers[layer - 1].weights()
     }
 
     /// Sets the layer biases for the given layer. The biases vector must have the size
     /// of the layer neurons. The layer index must be greater than 0 since the input layer
     /// does not have biases.
-    pub fn set_layer_biases(&mut self, layer: usize, biases: DVector<f64>) {
+    pub fn set_layer_biases(&mut self, layer: usize, biases: impl IntoBiasVector) {
+        if layer == 0 {
+            panic!("Invalid layer index");
+        }
+        self.layers[layer - 1].set_biases(biases.into_bias_vector());
+    }
+
+    /// Returns the biases vector of the given layer. The layer index must be greater than
+    /// 0 since the input layer does not have biases.
+    pub fn layer_biases(&self, layer: usize) -> &DVector<f64> {
         if layer == 0 {
             panic!("Invalid layer index");
         }
-        self.layers[layer - 1].set_biases(biases);
+        self.layers[layer - 1].biases()
     }
 
     /// Sets the weight of a specific neuron connection. The layer index must be greater
@@ -189,6 +381,204 @@ impl NeuralNetwork {
         self.activation_function = Some(activation_function);
     }
 
+    /// Returns the scaler applied to the inputs before the first layer, if any.
+    pub fn input_transform(&self) -> Option<&Scaler> {
+        self.input_transform.as_ref()
+    }
+
+    /// Attaches a fitted scaler that [`feed_forward`](Self::feed_forward) and the batch
+    /// methods apply to every sample before the first layer. The scaler is saved along
+    /// with the network by `dump_model`, so a loaded model takes raw features.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scaler was not fitted on as many features as the input layer has.
+    pub fn set_input_transform(&mut self, scaler: impl Into<Scaler>) {
+        let scaler = scaler.into();
+        assert_eq!(
+            scaler.len(),
+            self.input_layer_size(),
+            "the network takes {} inputs but the scaler was fitted on {} features",
+            self.input_layer_size(),
+            scaler.len()
+        );
+        self.input_transform = Some(scaler);
+    }
+
+    /// Removes the input transform, so inputs go to the first layer unchanged.
+    pub fn clear_input_transform(&mut self) -> Option<Scaler> {
+        self.input_transform.take()
+    }
+
+    /// Returns the metadata describing the network, if any.
+    pub fn metadata(&self) -> Option<&ModelMetadata> {
+        self.metadata.as_ref()
+    }
+
+    /// Attaches metadata to the network. It is saved along with the network by
+    /// `dump_model` and shown by `Display`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the metadata names the features or labels the outputs, but not one per
+    /// input or output neuron. A single-output network may also have two labels, one per
+    /// class of a binary classifier.
+    pub fn set_metadata(&mut self, metadata: ModelMetadata) {
+        let (inputs, outputs) = (
+            self.input_layer_size(),
+            self.layer_size(self.num_layers() - 1),
+        );
+        let labels = metadata.output_labels.len();
+        assert!(
+            metadata.feature_names.is_empty() || metadata.feature_names.len() == inputs,
+            "the network takes {} inputs but {} feature names were given",
+            inputs,
+            metadata.feature_names.len()
+        );
+        assert!(
+            labels == 0 || labels == outputs || (outputs == 1 && labels == 2),
+            "the network has {} outputs but {} output labels were given",
+            outputs,
+            labels
+        );
+        self.metadata = Some(metadata);
+    }
+
+    /// Removes the metadata from the network.
+    pub fn clear_metadata(&mut self) -> Option<ModelMetadata> {
+        self.metadata.take()
+    }
+
+    /// Feeds a sample given as feature name to value, ordered by the feature names in the
+    /// network [metadata](Self::set_metadata).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use only_brain::{ModelMetadata, NamedInputError, NeuralNetwork};
+    /// # use std::collections::HashMap;
+    /// let mut nn = NeuralNetwork::new(&vec![2, 1]);
+    /// nn.set_metadata(ModelMetadata {
+    ///     feature_names: vec!["x".into(), "y".into()],
+    ///     ..ModelMetadata::new("example")
+    /// });
+    ///
+    /// let sample = HashMap::from([("y".to_string(), 0.2), ("x".to_string(), 0.5)]);
+    /// assert_eq!(nn.feed_forward_named(&sample), Ok(nn.feed_forward(&vec![0.5, 0.2])));
+    ///
+    /// let typo = HashMap::from([("x".to_string(), 0.5), ("why".to_string(), 0.2)]);
+    /// assert_eq!(nn.feed_forward_named(&typo), Err(NamedInputError::MissingFeatures(vec!["y".into()])));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn feed_forward_named(
+        &self,
+        features: &std::collections::HashMap<String, f64>,
+    ) -> Result<Vec<f64>, NamedInputError> {
+        let names = match &self.metadata {
+            Some(metadata) if !metadata.feature_names.is_empty() => &metadata.feature_names,
+            _ => return Err(NamedInputError::NoFeatureNames),
+        };
+
+        let missing: Vec<String> = names
+            .iter()
+            .filter(|name| !features.contains_key(*name))
+            .cloned()
+            .collect();
+        if !missing.is_empty() {
+            return Err(NamedInputError::MissingFeatures(missing));
+        }
+        let mut unknown: Vec<String> = features
+            .keys()
+            .filter(|key| !names.contains(key))
+            .cloned()
+            .collect();
+        if !unknown.is_empty() {
+            unknown.sort();
+            return Err(NamedInputError::UnknownFeatures(unknown));
+        }
+
+        Ok(self.feed_forward(&names.iter().map(|name| features[name]).collect()))
+    }
+
+    /// Returns `true` if both networks have the same layer sizes, activation function and
+    /// input transform, and every weight and bias differs by at most `epsilon`.
+    ///
+    /// Use [`diff`](crate::diff) to find out where two networks differ.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use only_brain::NeuralNetwork;
+    /// let nn = NeuralNetwork::new(&vec![2, 1]);
+    /// let mut retrained = nn.clone();
+    /// retrained.set_weight(1, 0, 0, nn.get_weight(1, 0, 0) + 1e-9);
+    ///
+    /// assert!(nn.approx_eq(&retrained, 1e-6));
+    /// assert!(!nn.approx_eq(&retrained, 1e-12));
+    /// ```
+    pub fn approx_eq(&self, other: &Self, epsilon: f64) -> bool {
+        self.activation_function() == other.activation_function()
+            && self.input_transform == other.input_transform
+            && self.layers.len() == other.layers.len()
+            && self
+                .layers
+                .iter()
+                .zip(&other.layers)
+                .all(|(a, b)| a.approx_eq(b, epsilon))
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn layers(&self) -> &[Layer] {
+        &self.layers
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn layers_mut(&mut self) -> &mut [Layer] {
+        &mut self.layers
+    }
+
+    /// Checks that every layer has one row of weights and one bias per neuron, that each
+    /// layer takes as many inputs as the previous one has neurons, and that the input
+    /// transform fits the input layer. Decoded models are not validated by serde, so
+    /// [`load_model`](crate::load_model) runs this instead of handing back a network that
+    /// panics when it is run.
+    #[cfg(feature = "std")]
+    pub fn check_structure(&self) -> Result<(), String> {
+        if self.layers.is_empty() {
+            return Err("the model has no layers".to_string());
+        }
+        for layer in 1..self.num_layers() {
+            let (weights, biases) = (self.layer_weights(layer), self.layer_biases(layer));
+            let neurons = self.layer_size(layer);
+            if weights.nrows() != neurons || biases.len() != neurons {
+                return Err(format!(
+                    "layer {} has {} neurons but {} rows of weights and {} biases",
+                    layer,
+                    neurons,
+                    weights.nrows(),
+                    biases.len()
+                ));
+            }
+            if layer > 1 && weights.ncols() != self.layer_size(layer - 1) {
+                return Err(format!(
+                    "layer {} takes {} inputs but the previous layer has {} neurons",
+                    layer,
+                    weights.ncols(),
+                    self.layer_size(layer - 1)
+                ));
+            }
+        }
+        match &self.input_transform {
+            Some(scaler) if scaler.len() != self.input_layer_size() => Err(format!(
+                "the network takes {} inputs but the scaler was fitted on {} features",
+                self.input_layer_size(),
+                scaler.len()
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    #[cfg(feature = "std")]
     pub fn print(&self) {
         for layer in &self.layers {
             println!("{} {}", layer.weights(), layer.biases());
@@ -196,10 +586,32 @@ impl NeuralNetwork {
     }
 }
 
+/// Networks are equal when their layers, input transforms and metadata are, and they use
+/// the same activation function, whether it was set explicitly or is the default.
+impl PartialEq for NeuralNetwork {
+    fn eq(&self, other: &Self) -> bool {
+        self.layers == other.layers
+            && self.activation_function() == other.activation_function()
+            && self.input_transform == other.input_transform
+            && self.metadata == other.metadata
+    }
+}
+
 impl fmt::Display for NeuralNetwork {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "Neural Network")?;
+        if let Some(metadata) = &self.metadata {
+            write!(f, "{}", metadata)?;
+        }
         writeln!(f, "Activation Function: {:?}", self.activation_function())?;
+        if let Some(scaler) = &self.input_transform {
+            let name = match scaler {
+                Scaler::Standard(_) => "StandardScaler",
+                Scaler::MinMax(_) => "MinMaxScaler",
+                Scaler::Robust(_) => "RobustScaler",
+            };
+            writeln!(f, "Input Transform: {}", name)?;
+        }
         writeln!(f)?;
         writeln!(f, "Input Layer Size: {}", self.input_layer_size())?;
         writeln!(f)?;
@@ -221,7 +633,10 @@ mod tests {
     fn assert_all_close(actual: &[f64], expected: &[f64]) {
         assert_eq!(actual.len(), expected.len(), "length mismatch");
         for (i, (a, e)) in actual.iter().zip(expected).enumerate() {
-            assert!((a - e).abs() < EPSILON, "at index {i}: expected {e}, got {a}");
+            assert!(
+                (a - e).abs() < EPSILON,
+                "at index {i}: expected {e}, got {a}"
+            );
         }
     }
 
@@ -309,6 +724,57 @@ mod tests {
         assert_all_close(&nn.feed_forward(&vec![-1.0, 2.0]), &[0.0]);
     }
 
+    #[test]
+    #[should_panic(expected = "expected 2 inputs, got 3")]
+    fn feed_forward_bvector_rejects_the_wrong_size() {
+        fixed_network().feed_forward_bvector(&crate::bvector![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn feed_forward_many_keeps_the_sample_order() {
+        let nn = fixed_network();
+        let samples: Vec<Vec<f64>> = (0..1000).map(|i| vec![i as f64 / 1000.0, 0.5]).collect();
+
+        let outputs = nn.feed_forward_many(&samples);
+
+        assert_eq!(outputs.len(), samples.len());
+        for (sample, output) in samples.iter().zip(&outputs) {
+            assert_all_close(output, &nn.feed_forward(sample));
+        }
+    }
+
+    #[test]
+    fn feed_forward_batch_matches_feed_forward_row_by_row() {
+        let mut nn = NeuralNetwork::new(&vec![2, 3, 2]);
+        nn.set_activation_function(ActivationFunction::Tanh);
+        let inputs = DMatrix::from_fn(1000, 2, |row, col| {
+            (row as f64 - 500.0) / 250.0 + col as f64
+        });
+
+        let outputs = nn.feed_forward_batch(&inputs);
+
+        assert_eq!(outputs.shape(), (1000, 2));
+        for row in 0..inputs.nrows() {
+            let sample: Vec<f64> = inputs.row(row).iter().copied().collect();
+            let expected = nn.feed_forward(&sample);
+            let actual: Vec<f64> = outputs.row(row).iter().copied().collect();
+            assert_all_close(&actual, &expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 2 input columns")]
+    fn feed_forward_batch_rejects_the_wrong_number_of_columns() {
+        fixed_network().feed_forward_batch(&DMatrix::zeros(4, 3));
+    }
+
+    /// Batch inference shares one network between threads, so this must keep compiling.
+    #[test]
+    fn neural_network_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<NeuralNetwork>();
+    }
+
     #[test]
     fn set_and_get_weight_round_trip() {
         let mut nn = NeuralNetwork::new(&vec![2, 2]);
@@ -356,10 +822,119 @@ mod tests {
         );
     }
 
+    #[test]
+    fn a_clone_is_equal_and_approx_equal_to_its_original() {
+        let nn = fixed_network();
+        let copy = nn.clone();
+
+        assert_eq!(copy, nn);
+        assert!(copy.approx_eq(&nn, 0.0));
+    }
+
+    #[test]
+    fn approx_eq_respects_epsilon() {
+        let nn = fixed_network();
+        let mut nudged = nn.clone();
+        nudged.set_layer_biases(1, dvector![0.1 + 1e-6]);
+
+        assert!(nn.approx_eq(&nudged, 1e-5));
+        assert!(!nn.approx_eq(&nudged, 1e-7));
+    }
+
+    #[test]
+    fn approx_eq_rejects_different_shapes_and_activations() {
+        let nn = fixed_network();
+
+        assert!(!nn.approx_eq(&NeuralNetwork::new(&vec![2, 2]), f64::INFINITY));
+
+        let mut relu = nn.clone();
+        relu.set_activation_function(ActivationFunction::ReLU);
+        assert!(!nn.approx_eq(&relu, f64::INFINITY));
+    }
+
+    #[test]
+    fn the_input_transform_scales_single_and_batched_inputs() {
+        use crate::preprocessing::MinMaxScaler;
+
+        let mut nn = fixed_network();
+        nn.set_input_transform(MinMaxScaler::fit_rows(&[vec![0.0, 0.0], vec![10.0, 4.0]]));
+
+        // [10.0, 8.0] scales to [1.0, 2.0], the input of the hand-checked case above.
+        assert_all_close(&nn.feed_forward(&vec![10.0, 8.0]), &[sigmoid(0.1)]);
+        let batch = nn.feed_forward_batch(&dmatrix![10.0, 8.0; 0.0, 0.0]);
+        assert_all_close(&[batch[(0, 0)]], &[sigmoid(0.1)]);
+        assert!(nn.to_string().contains("Input Transform: MinMaxScaler"));
+
+        nn.clear_input_transform();
+        assert_all_close(&nn.feed_forward(&vec![1.0, 2.0]), &[sigmoid(0.1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the network takes 2 inputs but the scaler was fitted on 1 features")]
+    fn set_input_transform_rejects_the_wrong_width() {
+        use crate::preprocessing::StandardScaler;
+
+        fixed_network().set_input_transform(StandardScaler::fit_rows(&[vec![1.0]]));
+    }
+
+    #[test]
+    fn feed_forward_named_reports_missing_and_unknown_features() {
+        use std::collections::HashMap;
+
+        let mut nn = fixed_network();
+        let sample = HashMap::from([("b".to_string(), 2.0), ("a".to_string(), 1.0)]);
+        assert_eq!(
+            nn.feed_forward_named(&sample),
+            Err(NamedInputError::NoFeatureNames)
+        );
+
+        nn.set_metadata(ModelMetadata {
+            feature_names: vec!["a".into(), "b".into()],
+            ..ModelMetadata::new("fixed")
+        });
+        assert_all_close(&nn.feed_forward_named(&sample).unwrap(), &[sigmoid(0.1)]);
+
+        let extra = HashMap::from([
+            ("a".to_string(), 1.0),
+            ("b".to_string(), 2.0),
+            ("c".to_string(), 0.0),
+        ]);
+        assert_eq!(
+            nn.feed_forward_named(&extra),
+            Err(NamedInputError::UnknownFeatures(vec!["c".into()]))
+        );
+        assert!(nn.to_string().contains("Name: fixed\nFeatures: a, b\n"));
+    }
+
+    #[test]
+    #[should_panic(expected = "the network has 1 outputs but 3 output labels were given")]
+    fn set_metadata_rejects_the_wrong_number_of_labels() {
+        fixed_network().set_metadata(ModelMetadata {
+            output_labels: vec!["yes".into(), "no".into(), "maybe".into()],
+            ..ModelMetadata::new("fixed")
+        });
+    }
+
     #[test]
     fn display_reports_the_input_layer_size() {
         let nn = NeuralNetwork::new(&vec![4, 2, 1]);
 
         assert!(nn.to_string().contains("Input Layer Size: 4"));
     }
+
+    #[test]
+    fn layers_that_do_not_chain_fail_to_load() {
+        let layers = vec![
+            Layer::from_parts(dmatrix![1.0, 0.0; 0.0, 1.0], dvector![0.0, 0.0]),
+            Layer::from_parts(dmatrix![1.0, 1.0, 1.0], dvector![0.0]),
+        ];
+        let nn = NeuralNetwork::from_layers(layers, ActivationFunction::Sigmoid);
+        let message = "layer 2 takes 3 inputs but the previous layer has 2 neurons";
+        assert_eq!(nn.check_structure(), Err(message.to_string()));
+
+        let bytes = crate::dump_model_to_bytes(&nn).unwrap();
+        let error = crate::load_model_from_bytes(&bytes).unwrap_err();
+
+        assert_eq!(error.to_string(), message);
+    }
 }
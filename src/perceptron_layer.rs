@@ -0,0 +1,308 @@
+use alloc::vec;
+use core::fmt;
+
+use nalgebra::{DMatrix, DVector};
+
+use crate::activation_functions::{get_activation_function, ActivationFunction};
+use crate::layer::Layer;
+use crate::{BVector, NeuralNetwork, Perceptron};
+
+/// A layer of `M` perceptrons over the same `N` inputs, sharing one activation function.
+///
+/// It converts to and from a [`NeuralNetwork`] with a single layer of weights, so a
+/// one-vs-rest classifier can later grow hidden layers. Its weights are `f64` like the
+/// network's, and [`perceptron`](Self::perceptron) returns `Perceptron<f64, N>`.
+///
+/// # Example
+///
+/// ```
+/// use only_brain::{bvector, ActivationFunction, NeuralNetwork, PerceptronLayer};
+///
+/// let mut layer = PerceptronLayer::<2, 3>::new(ActivationFunction::BinaryStep);
+/// layer.set_weights(0, bvector![1.0, 0.0]);
+/// layer.set_weights(1, bvector![0.0, 1.0]);
+/// layer.set_weights(2, bvector![-1.0, -1.0]);
+///
+/// let output = layer.feed_forward(&bvector![0.5, -0.5]);
+/// assert_eq!(output, bvector![1.0, 0.0, 1.0]);
+///
+/// let nn = NeuralNetwork::from(&layer);
+/// assert_eq!(nn.feed_forward(&vec![0.5, -0.5]), vec![1.0, 0.0, 1.0]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PerceptronLayer<const N: usize, const M: usize> {
+    weights: [BVector<f64, N>; M],
+    biases: [f64; M],
+    activation_function: ActivationFunction,
+}
+
+impl<const N: usize, const M: usize> PerceptronLayer<N, M> {
+    /// Creates a layer with all weights and biases set to zero.
+    pub fn new(activation_function: ActivationFunction) -> Self {
+        Self {
+            weights: core::array::from_fn(|_| BVector::from_element(0.0)),
+            biases: [0.0; M],
+            activation_function,
+        }
+    }
+
+    /// Builds a layer from separately trained perceptrons, e.g. a one-vs-rest classifier.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the perceptrons do not all use the same activation function.
+    pub fn from_perceptrons(perceptrons: [Perceptron<f64, N>; M]) -> Self {
+        let activation_function = perceptrons.first().map_or(
+            ActivationFunction::default(),
+            Perceptron::activation_function,
+        );
+        assert!(
+            perceptrons
+                .iter()
+                .all(|perceptron| perceptron.activation_function() == activation_function),
+            "every perceptron must use the same activation function"
+        );
+
+        Self {
+            weights: core::array::from_fn(|neuron| perceptrons[neuron].weights().clone()),
+            biases: core::array::from_fn(|neuron| perceptrons[neuron].bias()),
+            activation_function,
+        }
+    }
+
+    pub fn set_weights(&mut self, neuron: usize, weights: BVector<f64, N>) {
+        self.weights[neuron] = weights;
+    }
+
+    pub fn set_bias(&mut self, neuron: usize, bias: f64) {
+        self.biases[neuron] = bias;
+    }
+
+    pub fn weights(&self, neuron: usize) -> &BVector<f64, N> {
+        &self.weights[neuron]
+    }
+
+    pub fn bias(&self, neuron: usize) -> f64 {
+        self.biases[neuron]
+    }
+
+    pub fn activation_function(&self) -> ActivationFunction {
+        self.activation_function
+    }
+
+    pub fn set_activation_function(&mut self, activation_function: ActivationFunction) {
+        self.activation_function = activation_function;
+    }
+
+    /// Returns a copy of one neuron as a standalone perceptron.
+    pub fn perceptron(&self, neuron: usize) -> Perceptron<f64, N> {
+        let mut perceptron = Perceptron::new(self.activation_function);
+        perceptron.set_weights(self.weights[neuron].clone());
+        perceptron.set_bias(self.biases[neuron]);
+        perceptron
+    }
+
+    /// Replaces the weights and bias of one neuron. The layer keeps its own activation
+    /// function.
+    pub fn set_perceptron(&mut self, neuron: usize, perceptron: &Perceptron<f64, N>) {
+        self.weights[neuron] = perceptron.weights().clone();
+        self.biases[neuron] = perceptron.bias();
+    }
+
+    pub fn feed_forward(&self, inputs: &BVector<f64, N>) -> BVector<f64, M> {
+        let activation = get_activation_function(self.activation_function);
+        BVector::from_array(core::array::from_fn(|neuron| {
+            activation(self.weights[neuron].dot(inputs) + self.biases[neuron])
+        }))
+    }
+}
+
+/// Error returned when a [`NeuralNetwork`] does not have the shape of a
+/// [`PerceptronLayer`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PerceptronLayerError {
+    /// The network has hidden layers.
+    NotSingleLayer { layers: usize },
+    SizeMismatch {
+        expected: (usize, usize),
+        got: (usize, usize),
+    },
+    /// The network scales its inputs first, which a [`PerceptronLayer`] cannot do.
+    InputTransform,
+}
+
+impl fmt::Display for PerceptronLayerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PerceptronLayerError::NotSingleLayer { layers } => {
+                write!(
+                    f,
+                    "expected an input and an output layer, the network has {} layers",
+                    layers
+                )
+            }
+            PerceptronLayerError::SizeMismatch { expected, got } => write!(
+                f,
+                "expected {} inputs and {} outputs, the network has {} and {}",
+                expected.0, expected.1, got.0, got.1
+            ),
+            PerceptronLayerError::InputTransform => {
+                write!(
+                    f,
+                    "the network has an input transform, which a perceptron layer cannot apply"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PerceptronLayerError {}
+
+impl<const N: usize, const M: usize> From<&PerceptronLayer<N, M>> for NeuralNetwork {
+    fn from(layer: &PerceptronLayer<N, M>) -> Self {
+        let weights = DMatrix::from_fn(M, N, |neuron, input| layer.weights[neuron].get(input));
+        let biases = DVector::from_column_slice(&layer.biases);
+
+        NeuralNetwork::from_layers(
+            vec![Layer::from_parts(weights, biases)],
+            layer.activation_function,
+        )
+    }
+}
+
+/// Fails if the network does not have exactly `N` inputs and `M` outputs, or if it has an
+/// input transform. The metadata is not carried over.
+impl<const N: usize, const M: usize> TryFrom<&NeuralNetwork> for PerceptronLayer<N, M> {
+    type Error = PerceptronLayerError;
+
+    fn try_from(network: &NeuralNetwork) -> Result<Self, Self::Error> {
+        if network.num_layers() != 2 {
+            return Err(PerceptronLayerError::NotSingleLayer {
+                layers: network.num_layers(),
+            });
+        }
+        let got = (network.layer_size(0), network.layer_size(1));
+        if got != (N, M) {
+            return Err(PerceptronLayerError::SizeMismatch {
+                expected: (N, M),
+                got,
+            });
+        }
+        if network.input_transform().is_some() {
+            return Err(PerceptronLayerError::InputTransform);
+        }
+
+        let weights = network.layer_weights(1);
+        let biases = network.layer_biases(1);
+        Ok(Self {
+            weights: core::array::from_fn(|neuron| {
+                BVector::from_array(core::array::from_fn(|input| weights[(neuron, input)]))
+            }),
+            biases: core::array::from_fn(|neuron| biases[neuron]),
+            activation_function: network.activation_function(),
+        })
+    }
+}
+
+impl<const N: usize, const M: usize> fmt::Display for PerceptronLayer<N, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Perceptron Layer:")?;
+        writeln!(f, "Activation Function: {:?}", self.activation_function)?;
+        writeln!(f)?;
+        writeln!(f, "Inputs Size: {}", N)?;
+        writeln!(f, "Outputs Size: {}", M)?;
+        for (neuron, (weights, bias)) in self.weights.iter().zip(&self.biases).enumerate() {
+            writeln!(
+                f,
+                "Perceptron {}: Weights: {:?}, Bias: {}",
+                neuron, weights, bias
+            )?;
+        }
+        writeln!(f)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bvector, StandardScaler};
+
+    fn layer() -> PerceptronLayer<2, 2> {
+        let mut layer = PerceptronLayer::new(ActivationFunction::Tanh);
+        layer.set_weights(0, bvector![0.5, -0.25]);
+        layer.set_bias(0, 0.1);
+        layer.set_weights(1, bvector![1.0, 2.0]);
+        layer.set_bias(1, -0.3);
+        layer
+    }
+
+    #[test]
+    fn feed_forward_matches_the_individual_perceptrons() {
+        let layer = layer();
+        let inputs = bvector![0.4, 0.7];
+
+        let outputs = layer.feed_forward(&inputs);
+
+        for neuron in 0..2 {
+            assert_eq!(
+                outputs.get(neuron),
+                layer.perceptron(neuron).feed_forward(&inputs)
+            );
+        }
+    }
+
+    #[test]
+    fn converts_to_a_neural_network_and_back() {
+        let layer = layer();
+
+        let nn = NeuralNetwork::from(&layer);
+        let back = PerceptronLayer::<2, 2>::try_from(&nn).unwrap();
+
+        assert_eq!(nn.activation_function(), ActivationFunction::Tanh);
+        assert_eq!(nn.get_weight(1, 1, 0), 1.0);
+        assert_eq!(
+            nn.feed_forward(&vec![0.4, 0.7])[1],
+            layer.feed_forward(&bvector![0.4, 0.7]).get(1)
+        );
+        assert_eq!(back, layer);
+    }
+
+    #[test]
+    fn from_perceptrons_keeps_each_neuron() {
+        let layer = layer();
+
+        let rebuilt = PerceptronLayer::from_perceptrons([layer.perceptron(0), layer.perceptron(1)]);
+
+        assert_eq!(rebuilt, layer);
+    }
+
+    #[test]
+    fn networks_of_the_wrong_shape_are_rejected() {
+        let layer = layer();
+        let nn = NeuralNetwork::from(&layer);
+
+        let error = PerceptronLayer::<3, 2>::try_from(&nn).unwrap_err();
+
+        assert_eq!(
+            error,
+            PerceptronLayerError::SizeMismatch {
+                expected: (3, 2),
+                got: (2, 2)
+            }
+        );
+    }
+
+    /// Dropping the scaler would silently change the outputs.
+    #[test]
+    fn networks_with_an_input_transform_are_rejected() {
+        let mut nn = NeuralNetwork::from(&layer());
+        nn.set_input_transform(StandardScaler::fit_rows(&[vec![0.0, 1.0], vec![2.0, 3.0]]));
+
+        let error = PerceptronLayer::<2, 2>::try_from(&nn).unwrap_err();
+
+        assert_eq!(error, PerceptronLayerError::InputTransform);
+    }
+}
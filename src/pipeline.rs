@@ -0,0 +1,367 @@
+use serde::{Deserialize, Serialize};
+
+use crate::preprocessing::Scaler;
+use crate::NeuralNetwork;
+
+/// A step applied to the network outputs by a [`Pipeline`], in the order they were added.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum OutputTransform {
+    /// Maps scaled outputs back to target units, for networks trained on scaled targets.
+    InverseScale(Scaler),
+    /// Clamps every output to `[min, max]`.
+    Clip { min: f64, max: f64 },
+    /// Turns every output into `1.0` if it is at least the threshold and `0.0` otherwise.
+    Threshold(f64),
+    /// Returns the label of the largest output. This must be the last transform.
+    ArgmaxLabel(Vec<String>),
+}
+
+/// The result of [`Pipeline::predict`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Prediction {
+    Values(Vec<f64>),
+    Label(String),
+}
+
+impl Prediction {
+    /// Returns the output values, unless the pipeline ends with
+    /// [`OutputTransform::ArgmaxLabel`].
+    pub fn values(&self) -> Option<&[f64]> {
+        match self {
+            Prediction::Values(values) => Some(values),
+            Prediction::Label(_) => None,
+        }
+    }
+
+    /// Returns the predicted label, if the pipeline ends with
+    /// [`OutputTransform::ArgmaxLabel`].
+    pub fn label(&self) -> Option<&str> {
+        match self {
+            Prediction::Values(_) => None,
+            Prediction::Label(label) => Some(label),
+        }
+    }
+}
+
+/// Input scalers, a network and output transforms that turn raw features into final
+/// predictions. The whole pipeline is saved as one file with `dump_pipeline`.
+///
+/// The input scalers take the place of the network's own
+/// [input transform](NeuralNetwork::set_input_transform), so a network that has one cannot
+/// be given any.
+///
+/// # Example
+///
+/// ```
+/// # use only_brain::{NeuralNetwork, OutputTransform, Pipeline, StandardScaler};
+/// let scaler = StandardScaler::fit_rows(&[vec![120.0, 3.0], vec![80.0, 1.0]]);
+/// let pipeline = Pipeline::new(NeuralNetwork::new(&vec![2, 4, 3]))
+///     .with_input_transform(scaler)
+///     .with_output_transform(OutputTransform::ArgmaxLabel(vec![
+///         "low".to_string(),
+///         "medium".to_string(),
+///         "high".to_string(),
+///     ]));
+///
+/// let prediction = pipeline.predict(&[100.0, 2.0]);
+/// assert!(["low", "medium", "high"].contains(&prediction.label().unwrap()));
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Pipeline {
+    input_transforms: Vec<Scaler>,
+    network: NeuralNetwork,
+    output_transforms: Vec<OutputTransform>,
+}
+
+impl Pipeline {
+    pub fn new(network: NeuralNetwork) -> Self {
+        Self {
+            input_transforms: Vec::new(),
+            network,
+            output_transforms: Vec::new(),
+        }
+    }
+
+    /// Adds a scaler applied to the features after the ones already added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scaler was not fitted on as many features as the network takes, or
+    /// if the network has its own [input transform](NeuralNetwork::set_input_transform),
+    /// which would scale the features a second time.
+    pub fn with_input_transform(mut self, scaler: impl Into<Scaler>) -> Self {
+        let scaler = scaler.into();
+        if let Some(error) = self.input_transform_error(&scaler) {
+            panic!("{}", error);
+        }
+        self.input_transforms.push(scaler);
+        self
+    }
+
+    /// Adds a transform applied to the outputs after the ones already added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a transform follows [`OutputTransform::ArgmaxLabel`], if a scaler or
+    /// label list does not have one entry per output neuron, or if the bounds of
+    /// [`OutputTransform::Clip`] are NaN or `min > max`.
+    pub fn with_output_transform(mut self, transform: OutputTransform) -> Self {
+        if let Some(error) = self.output_transform_error(self.output_transforms.last(), &transform)
+        {
+            panic!("{}", error);
+        }
+        self.output_transforms.push(transform);
+        self
+    }
+
+    fn input_transform_error(&self, scaler: &Scaler) -> Option<String> {
+        if self.network.input_transform().is_some() {
+            return Some("the network already has an input transform".to_string());
+        }
+        let inputs = self.network.layer_size(0);
+        (scaler.len() != inputs).then(|| {
+            format!(
+                "the network takes {} inputs but the scaler was fitted on {} features",
+                inputs,
+                scaler.len()
+            )
+        })
+    }
+
+    fn output_transform_error(
+        &self,
+        previous: Option<&OutputTransform>,
+        transform: &OutputTransform,
+    ) -> Option<String> {
+        if matches!(previous, Some(OutputTransform::ArgmaxLabel(_))) {
+            return Some("ArgmaxLabel must be the last output transform".to_string());
+        }
+        let outputs = self.network.layer_size(self.network.num_layers() - 1);
+        let expected = match transform {
+            OutputTransform::InverseScale(scaler) => scaler.len(),
+            OutputTransform::ArgmaxLabel(labels) => labels.len(),
+            // `f64::clamp` panics on these, so catch them here rather than in `predict`.
+            OutputTransform::Clip { min, max } if min.is_nan() || max.is_nan() || min > max => {
+                return Some(format!(
+                    "clip bounds must be numbers with min <= max, got [{}, {}]",
+                    min, max
+                ));
+            }
+            OutputTransform::Clip { .. } | OutputTransform::Threshold(_) => return None,
+        };
+        (expected != outputs).then(|| {
+            format!(
+                "the network has {} outputs but the transform expects {}",
+                outputs, expected
+            )
+        })
+    }
+
+    /// Checks everything the builder methods assert, since a deserialized pipeline did
+    /// not go through them. Loading a pipeline runs this.
+    pub(crate) fn check(&self) -> Result<(), String> {
+        self.network.check_structure()?;
+        if let Some(error) = self
+            .input_transforms
+            .iter()
+            .find_map(|scaler| self.input_transform_error(scaler))
+        {
+            return Err(error);
+        }
+        let previous = core::iter::once(None).chain(self.output_transforms.iter().map(Some));
+        match previous
+            .zip(&self.output_transforms)
+            .find_map(|(previous, transform)| self.output_transform_error(previous, transform))
+        {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    pub fn network(&self) -> &NeuralNetwork {
+        &self.network
+    }
+
+    pub fn input_transforms(&self) -> &[Scaler] {
+        &self.input_transforms
+    }
+
+    pub fn output_transforms(&self) -> &[OutputTransform] {
+        &self.output_transforms
+    }
+
+    /// Runs the raw `features` through every step of the pipeline.
+    pub fn predict(&self, features: &[f64]) -> Prediction {
+        let mut values = features.to_vec();
+        for scaler in &self.input_transforms {
+            values = scaler.transform(&values);
+        }
+
+        values = self.network.feed_forward(&values);
+
+        for transform in &self.output_transforms {
+            match transform {
+                OutputTransform::InverseScale(scaler) => values = scaler.inverse_transform(&values),
+                OutputTransform::Clip { min, max } => {
+                    values.iter_mut().for_each(|v| *v = v.clamp(*min, *max))
+                }
+                OutputTransform::Threshold(threshold) => values
+                    .iter_mut()
+                    .for_each(|v| *v = if *v >= *threshold { 1.0 } else { 0.0 }),
+                OutputTransform::ArgmaxLabel(labels) => {
+                    let best = values
+                        .iter()
+                        .enumerate()
+                        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                        .map(|(index, _)| index)
+                        .unwrap_or_default();
+                    return Prediction::Label(labels[best].clone());
+                }
+            }
+        }
+
+        Prediction::Values(values)
+    }
+
+    /// Runs [`predict`](Self::predict) over every sample, keeping their order.
+    pub fn predict_many(&self, samples: &[Vec<f64>]) -> Vec<Prediction> {
+        samples.iter().map(|sample| self.predict(sample)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ActivationFunction, MinMaxScaler};
+    use nalgebra::{dmatrix, dvector};
+
+    /// A 2 -> 2 identity network, so the transforms can be checked by hand.
+    fn identity_pipeline() -> Pipeline {
+        let mut nn = NeuralNetwork::new(&vec![2, 2]);
+        nn.set_activation_function(ActivationFunction::ReLU);
+        nn.set_layer_weights(1, dmatrix![1.0, 0.0; 0.0, 1.0]);
+        nn.set_layer_biases(1, dvector![0.0, 0.0]);
+        Pipeline::new(nn)
+    }
+
+    #[test]
+    fn transforms_run_in_order_around_the_network() {
+        let targets = MinMaxScaler::fit_rows(&[vec![0.0, 100.0], vec![10.0, 200.0]]);
+        let pipeline = identity_pipeline()
+            .with_input_transform(MinMaxScaler::fit_rows(&[vec![0.0, 0.0], vec![4.0, 4.0]]))
+            .with_output_transform(OutputTransform::InverseScale(targets.into()))
+            .with_output_transform(OutputTransform::Clip {
+                min: 0.0,
+                max: 150.0,
+            });
+
+        // [2, 1] scales to [0.5, 0.25], then maps to [5, 125] in target units.
+        assert_eq!(
+            pipeline.predict(&[2.0, 1.0]),
+            Prediction::Values(vec![5.0, 125.0])
+        );
+        // [4, 4] maps to [10, 200] and the second output is clipped.
+        assert_eq!(
+            pipeline.predict(&[4.0, 4.0]).values(),
+            Some(&[10.0, 150.0][..])
+        );
+    }
+
+    #[test]
+    fn threshold_and_argmax_label() {
+        let thresholded =
+            identity_pipeline().with_output_transform(OutputTransform::Threshold(0.5));
+        let labelled =
+            identity_pipeline().with_output_transform(OutputTransform::ArgmaxLabel(vec![
+                "cat".into(),
+                "dog".into(),
+            ]));
+
+        assert_eq!(
+            thresholded.predict(&[0.7, 0.2]),
+            Prediction::Values(vec![1.0, 0.0])
+        );
+        let labels: Vec<_> = labelled.predict_many(&[vec![0.1, 0.9], vec![3.0, 1.0]]);
+        assert_eq!(labels[0].label(), Some("dog"));
+        assert_eq!(labels[1].label(), Some("cat"));
+    }
+
+    #[test]
+    #[should_panic(expected = "ArgmaxLabel must be the last output transform")]
+    fn nothing_may_follow_argmax_label() {
+        identity_pipeline()
+            .with_output_transform(OutputTransform::ArgmaxLabel(vec!["a".into(), "b".into()]))
+            .with_output_transform(OutputTransform::Threshold(0.5));
+    }
+
+    #[test]
+    #[should_panic(expected = "the network has 2 outputs but the transform expects 3")]
+    fn label_count_must_match_the_outputs() {
+        identity_pipeline().with_output_transform(OutputTransform::ArgmaxLabel(vec![
+            "a".into(),
+            "b".into(),
+            "c".into(),
+        ]));
+    }
+
+    #[test]
+    #[should_panic(expected = "the network already has an input transform")]
+    fn networks_that_scale_their_own_inputs_take_no_pipeline_scaler() {
+        let scaler = MinMaxScaler::fit_rows(&[vec![0.0, 0.0], vec![4.0, 4.0]]);
+        let mut nn = identity_pipeline().network().clone();
+        nn.set_input_transform(scaler.clone());
+
+        Pipeline::new(nn).with_input_transform(scaler);
+    }
+
+    #[test]
+    fn check_catches_what_the_builder_would_reject() {
+        let valid = identity_pipeline().with_output_transform(OutputTransform::ArgmaxLabel(vec![
+            "cat".into(),
+            "dog".into(),
+        ]));
+        assert_eq!(valid.check(), Ok(()));
+
+        let mut after_argmax = valid;
+        after_argmax
+            .output_transforms
+            .push(OutputTransform::Threshold(0.5));
+        assert_eq!(
+            after_argmax.check(),
+            Err("ArgmaxLabel must be the last output transform".to_string())
+        );
+
+        let mut inverted = identity_pipeline();
+        inverted
+            .output_transforms
+            .push(OutputTransform::Clip { min: 1.0, max: 0.0 });
+        assert_eq!(
+            inverted.check(),
+            Err("clip bounds must be numbers with min <= max, got [1, 0]".to_string())
+        );
+
+        let mut narrow = identity_pipeline();
+        narrow
+            .input_transforms
+            .push(MinMaxScaler::fit_rows(&[vec![0.0], vec![1.0]]).into());
+        assert_eq!(
+            narrow.check(),
+            Err("the network takes 2 inputs but the scaler was fitted on 1 features".to_string())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "clip bounds must be numbers with min <= max, got [1, 0]")]
+    fn clip_rejects_inverted_bounds() {
+        identity_pipeline().with_output_transform(OutputTransform::Clip { min: 1.0, max: 0.0 });
+    }
+
+    #[test]
+    #[should_panic(expected = "clip bounds must be numbers with min <= max, got [NaN, 1]")]
+    fn clip_rejects_nan_bounds() {
+        identity_pipeline().with_output_transform(OutputTransform::Clip {
+            min: f64::NAN,
+            max: 1.0,
+        });
+    }
+}
@@ -0,0 +1,309 @@
+use alloc::vec::Vec;
+
+use nalgebra::{ComplexField, DMatrix};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "std")]
+use crate::Dataset;
+
+/// Returns one column of `rows` at a time.
+fn columns(rows: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    assert!(!rows.is_empty(), "cannot fit a scaler on zero rows");
+    let width = rows[0].len();
+    (0..width)
+        .map(|col| rows.iter().map(|row| row[col]).collect())
+        .collect()
+}
+
+/// A constant column would divide by zero, so it is left unscaled instead.
+fn non_zero(scale: f64) -> f64 {
+    if scale == 0.0 {
+        1.0
+    } else {
+        scale
+    }
+}
+
+/// Linear interpolation between the closest ranks, like NumPy's default percentile.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let lower = position as usize;
+    let upper = (lower + 1).min(sorted.len() - 1);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+/// Centers each feature on its mean and divides it by its standard deviation.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StandardScaler {
+    pub means: Vec<f64>,
+    pub scales: Vec<f64>,
+}
+
+impl StandardScaler {
+    /// Fits the scaler on the inputs of `dataset`.
+    #[cfg(feature = "std")]
+    pub fn fit(dataset: &Dataset) -> Self {
+        Self::fit_rows(dataset.inputs())
+    }
+
+    /// Fits the scaler on one sample per row.
+    pub fn fit_rows(rows: &[Vec<f64>]) -> Self {
+        let (means, scales) = columns(rows)
+            .iter()
+            .map(|column| {
+                let n = column.len() as f64;
+                let mean = column.iter().sum::<f64>() / n;
+                let variance = column.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / n;
+                (mean, non_zero(ComplexField::sqrt(variance)))
+            })
+            .unzip();
+
+        Self { means, scales }
+    }
+}
+
+/// Maps each feature linearly onto `[0, 1]` using the smallest and largest value seen.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MinMaxScaler {
+    pub mins: Vec<f64>,
+    pub ranges: Vec<f64>,
+}
+
+impl MinMaxScaler {
+    /// Fits the scaler on the inputs of `dataset`.
+    #[cfg(feature = "std")]
+    pub fn fit(dataset: &Dataset) -> Self {
+        Self::fit_rows(dataset.inputs())
+    }
+
+    /// Fits the scaler on one sample per row.
+    pub fn fit_rows(rows: &[Vec<f64>]) -> Self {
+        let (mins, ranges) = columns(rows)
+            .iter()
+            .map(|column| {
+                let min = column.iter().copied().fold(f64::INFINITY, f64::min);
+                let max = column.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                (min, non_zero(max - min))
+            })
+            .unzip();
+
+        Self { mins, ranges }
+    }
+}
+
+/// Centers each feature on its median and divides it by its interquartile range, which
+/// keeps outliers from dominating the scale.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RobustScaler {
+    pub medians: Vec<f64>,
+    pub iqrs: Vec<f64>,
+}
+
+impl RobustScaler {
+    /// Fits the scaler on the inputs of `dataset`.
+    #[cfg(feature = "std")]
+    pub fn fit(dataset: &Dataset) -> Self {
+        Self::fit_rows(dataset.inputs())
+    }
+
+    /// Fits the scaler on one sample per row.
+    pub fn fit_rows(rows: &[Vec<f64>]) -> Self {
+        let (medians, iqrs) = columns(rows)
+            .into_iter()
+            .map(|mut column| {
+                column.sort_by(f64::total_cmp);
+                let iqr = quantile(&column, 0.75) - quantile(&column, 0.25);
+                (quantile(&column, 0.5), non_zero(iqr))
+            })
+            .unzip();
+
+        Self { medians, iqrs }
+    }
+}
+
+/// A fitted feature scaler. Attach one to a network with
+/// [`NeuralNetwork::set_input_transform`] and `feed_forward` scales its inputs before
+/// the first layer.
+///
+/// # Example
+///
+/// ```
+/// # use only_brain::{Dataset, NeuralNetwork, StandardScaler};
+/// let train = Dataset::new(
+///     vec![vec![100.0, 0.1], vec![200.0, 0.3], vec![300.0, 0.2]],
+///     vec![vec![0.0], vec![1.0], vec![1.0]],
+/// );
+///
+/// let mut nn = NeuralNetwork::new(&vec![2, 1]);
+/// nn.set_input_transform(StandardScaler::fit(&train));
+///
+/// // Raw, unscaled features go in.
+/// let output = nn.feed_forward(&vec![250.0, 0.15]);
+/// ```
+///
+/// [`NeuralNetwork::set_input_transform`]: crate::NeuralNetwork::set_input_transform
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Scaler {
+    Standard(StandardScaler),
+    MinMax(MinMaxScaler),
+    Robust(RobustScaler),
+}
+
+impl Scaler {
+    /// Returns `(offset, scale)` so that `scaled = (value - offset) / scale`.
+    fn parameters(&self) -> (&[f64], &[f64]) {
+        match self {
+            Scaler::Standard(scaler) => (&scaler.means, &scaler.scales),
+            Scaler::MinMax(scaler) => (&scaler.mins, &scaler.ranges),
+            Scaler::Robust(scaler) => (&scaler.medians, &scaler.iqrs),
+        }
+    }
+
+    /// Returns the number of features the scaler was fitted on.
+    pub fn len(&self) -> usize {
+        self.parameters().0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Scales one sample.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sample does not have one value per fitted feature.
+    pub fn transform(&self, values: &[f64]) -> Vec<f64> {
+        let (offsets, scales) = self.parameters();
+        assert_eq!(
+            values.len(),
+            offsets.len(),
+            "the scaler was fitted on {} features, got {}",
+            offsets.len(),
+            values.len()
+        );
+        values
+            .iter()
+            .zip(offsets.iter().zip(scales))
+            .map(|(value, (offset, scale))| (value - offset) / scale)
+            .collect()
+    }
+
+    /// Undoes [`transform`](Self::transform).
+    pub fn inverse_transform(&self, values: &[f64]) -> Vec<f64> {
+        let (offsets, scales) = self.parameters();
+        assert_eq!(
+            values.len(),
+            offsets.len(),
+            "the scaler was fitted on {} features, got {}",
+            offsets.len(),
+            values.len()
+        );
+        values
+            .iter()
+            .zip(offsets.iter().zip(scales))
+            .map(|(value, (offset, scale))| value * scale + offset)
+            .collect()
+    }
+
+    /// Scales every row of a batch in place.
+    pub(crate) fn transform_rows(&self, rows: &mut DMatrix<f64>) {
+        let (offsets, scales) = self.parameters();
+        assert_eq!(
+            rows.ncols(),
+            offsets.len(),
+            "the scaler was fitted on {} features, got {}",
+            offsets.len(),
+            rows.ncols()
+        );
+        for (col, mut column) in rows.column_iter_mut().enumerate() {
+            column.apply(|value| *value = (*value - offsets[col]) / scales[col]);
+        }
+    }
+}
+
+impl From<StandardScaler> for Scaler {
+    fn from(scaler: StandardScaler) -> Self {
+        Scaler::Standard(scaler)
+    }
+}
+
+impl From<MinMaxScaler> for Scaler {
+    fn from(scaler: MinMaxScaler) -> Self {
+        Scaler::MinMax(scaler)
+    }
+}
+
+impl From<RobustScaler> for Scaler {
+    fn from(scaler: RobustScaler) -> Self {
+        Scaler::Robust(scaler)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    const EPSILON: f64 = 1e-12;
+
+    fn rows() -> Vec<Vec<f64>> {
+        vec![
+            vec![1.0, 5.0],
+            vec![2.0, 5.0],
+            vec![3.0, 5.0],
+            vec![10.0, 5.0],
+        ]
+    }
+
+    fn assert_all_close(actual: &[f64], expected: &[f64]) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!(
+                (a - e).abs() < EPSILON,
+                "expected {expected:?}, got {actual:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn standard_scaler_centers_and_scales() {
+        let scaler = StandardScaler::fit_rows(&rows());
+
+        assert_all_close(&scaler.means, &[4.0, 5.0]);
+        assert_all_close(&scaler.scales, &[12.5f64.sqrt(), 1.0]);
+    }
+
+    #[test]
+    fn min_max_scaler_maps_onto_the_unit_interval() {
+        let scaler = Scaler::from(MinMaxScaler::fit_rows(&rows()));
+
+        assert_all_close(&scaler.transform(&[1.0, 5.0]), &[0.0, 0.0]);
+        assert_all_close(&scaler.transform(&[10.0, 5.0]), &[1.0, 0.0]);
+    }
+
+    #[test]
+    fn robust_scaler_uses_median_and_interquartile_range() {
+        let scaler = RobustScaler::fit_rows(&rows());
+
+        assert_all_close(&scaler.medians, &[2.5, 5.0]);
+        assert_all_close(&scaler.iqrs, &[4.75 - 1.75, 1.0]);
+    }
+
+    #[test]
+    fn inverse_transform_undoes_transform() {
+        for scaler in [
+            Scaler::from(StandardScaler::fit_rows(&rows())),
+            Scaler::from(MinMaxScaler::fit_rows(&rows())),
+            Scaler::from(RobustScaler::fit_rows(&rows())),
+        ] {
+            let scaled = scaler.transform(&[7.0, -3.0]);
+            assert_all_close(&scaler.inverse_transform(&scaled), &[7.0, -3.0]);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "fitted on 2 features, got 3")]
+    fn transform_rejects_the_wrong_width() {
+        Scaler::from(StandardScaler::fit_rows(&rows())).transform(&[1.0, 2.0, 3.0]);
+    }
+}
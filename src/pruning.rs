@@ -0,0 +1,201 @@
+use nalgebra::DMatrix;
+
+use crate::NeuralNetwork;
+
+/// Which weights [`NeuralNetwork::prune`] removes.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PruneCriterion {
+    /// Every weight whose absolute value is below the threshold.
+    Threshold(f64),
+    /// The given fraction (`0.0..=1.0`) of weights with the smallest absolute value.
+    Fraction(f64),
+}
+
+/// Whether a [`PruneCriterion::Fraction`] is applied to each layer on its own or to all
+/// weights of the network at once. Thresholds are absolute, so they behave the same way
+/// in both scopes.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum PruneScope {
+    #[default]
+    PerLayer,
+    Global,
+}
+
+/// Result of [`NeuralNetwork::prune`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PruneReport {
+    /// One mask per layer, shaped like its weights matrix. `true` marks a kept weight.
+    pub masks: Vec<DMatrix<bool>>,
+    pub pruned: usize,
+    pub total: usize,
+}
+
+impl PruneReport {
+    /// Fraction of the weights that are zero after pruning.
+    pub fn sparsity(&self) -> f64 {
+        self.pruned as f64 / self.total as f64
+    }
+}
+
+/// Marks the `fraction` of `values` with the smallest magnitude. Exactly
+/// `round(fraction * len)` entries are marked, even when magnitudes tie.
+fn smallest_fraction(values: &[f64], fraction: f64) -> Vec<bool> {
+    assert!(
+        (0.0..=1.0).contains(&fraction),
+        "prune fraction must be between 0 and 1, got {fraction}"
+    );
+    let count = (fraction * values.len() as f64).round() as usize;
+
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].abs().total_cmp(&values[b].abs()));
+
+    let mut pruned = vec![false; values.len()];
+    for &index in order.iter().take(count) {
+        pruned[index] = true;
+    }
+    pruned
+}
+
+impl NeuralNetwork {
+    /// Zeroes weights by magnitude and returns the resulting masks. Biases are left as
+    /// they are.
+    ///
+    /// Keep the masks around and pass them to [`apply_masks`](Self::apply_masks) after
+    /// updating weights, so pruned connections stay at zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use only_brain::{NeuralNetwork, PruneCriterion, PruneScope};
+    /// let mut nn = NeuralNetwork::new(&vec![8, 8, 2]);
+    /// let report = nn.prune(PruneCriterion::Fraction(0.75), PruneScope::Global);
+    ///
+    /// assert_eq!(report.pruned, 60);
+    /// assert!((report.sparsity() - 0.75).abs() < 1e-12);
+    /// ```
+    pub fn prune(&mut self, criterion: PruneCriterion, scope: PruneScope) -> PruneReport {
+        let layer_pruned: Vec<Vec<bool>> = match (criterion, scope) {
+            (PruneCriterion::Threshold(threshold), _) => self
+                .layers()
+                .iter()
+                .map(|layer| {
+                    layer
+                        .weights()
+                        .iter()
+                        .map(|w| w.abs() < threshold)
+                        .collect()
+                })
+                .collect(),
+            (PruneCriterion::Fraction(fraction), PruneScope::PerLayer) => self
+                .layers()
+                .iter()
+                .map(|layer| smallest_fraction(layer.weights().as_slice(), fraction))
+                .collect(),
+            (PruneCriterion::Fraction(fraction), PruneScope::Global) => {
+                let all: Vec<f64> = self
+                    .layers()
+                    .iter()
+                    .flat_map(|layer| layer.weights().iter().copied())
+                    .collect();
+                let mut pruned = smallest_fraction(&all, fraction).into_iter();
+                self.layers()
+                    .iter()
+                    .map(|layer| pruned.by_ref().take(layer.weights().len()).collect())
+                    .collect()
+            }
+        };
+
+        let masks: Vec<DMatrix<bool>> = self
+            .layers()
+            .iter()
+            .zip(&layer_pruned)
+            .map(|(layer, pruned)| {
+                let (rows, cols) = layer.shape();
+                DMatrix::from_iterator(rows, cols, pruned.iter().map(|&p| !p))
+            })
+            .collect();
+
+        self.apply_masks(&masks);
+
+        PruneReport {
+            pruned: layer_pruned.iter().flatten().filter(|&&p| p).count(),
+            total: layer_pruned.iter().map(Vec::len).sum(),
+            masks,
+        }
+    }
+
+    /// Zeroes every weight whose mask entry is `false`. There must be one mask per layer,
+    /// shaped like that layer's weights matrix.
+    pub fn apply_masks(&mut self, masks: &[DMatrix<bool>]) {
+        assert_eq!(
+            masks.len(),
+            self.layers().len(),
+            "expected one mask per layer"
+        );
+
+        for (layer, mask) in self.layers_mut().iter_mut().zip(masks) {
+            assert_eq!(layer.shape(), mask.shape(), "Incompatible mask size");
+            let masked = layer
+                .weights()
+                .zip_map(mask, |w, keep| if keep { w } else { 0.0 });
+            layer.set_weights(masked);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::dmatrix;
+
+    fn network() -> NeuralNetwork {
+        let mut nn = NeuralNetwork::new(&vec![2, 2, 1]);
+        nn.set_layer_weights(1, dmatrix![0.1, -0.5; 0.9, -0.05]);
+        nn.set_layer_weights(2, dmatrix![0.2, -0.3]);
+        nn
+    }
+
+    #[test]
+    fn threshold_zeroes_small_weights_and_masks_them() {
+        let mut nn = network();
+        let report = nn.prune(PruneCriterion::Threshold(0.15), PruneScope::PerLayer);
+
+        assert_eq!(report.pruned, 2);
+        assert_eq!(report.total, 6);
+        assert_eq!(nn.get_weight(1, 0, 0), 0.0);
+        assert_eq!(nn.get_weight(1, 1, 1), 0.0);
+        assert_eq!(nn.get_weight(1, 0, 1), -0.5);
+        assert_eq!(report.masks[0], dmatrix![false, true; true, false]);
+    }
+
+    #[test]
+    fn per_layer_fraction_prunes_each_layer_separately() {
+        let mut nn = network();
+        let report = nn.prune(PruneCriterion::Fraction(0.5), PruneScope::PerLayer);
+
+        assert_eq!(report.pruned, 3);
+        assert_eq!(report.masks[1], dmatrix![false, true]);
+    }
+
+    #[test]
+    fn global_fraction_ranks_weights_across_layers() {
+        let mut nn = network();
+        let report = nn.prune(PruneCriterion::Fraction(0.5), PruneScope::Global);
+
+        assert_eq!(report.pruned, 3);
+        assert_eq!(report.masks[0], dmatrix![false, true; true, false]);
+        assert_eq!(report.masks[1], dmatrix![false, true]);
+    }
+
+    #[test]
+    fn apply_masks_keeps_pruned_weights_at_zero_after_an_update() {
+        let mut nn = network();
+        let report = nn.prune(PruneCriterion::Threshold(0.15), PruneScope::PerLayer);
+
+        nn.set_layer_weights(1, dmatrix![1.0, 1.0; 1.0, 1.0]);
+        nn.apply_masks(&report.masks);
+
+        assert_eq!(nn.get_weight(1, 0, 0), 0.0);
+        assert_eq!(nn.get_weight(1, 1, 0), 1.0);
+    }
+}
@@ -0,0 +1,373 @@
+use serde::{Deserialize, Serialize};
+
+use crate::activation_functions::{get_activation_function, ActivationFunction};
+use crate::layer::Layer;
+use crate::preprocessing::Scaler;
+use crate::NeuralNetwork;
+
+/// How many scale factors a quantized weights matrix gets.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Serialize, Deserialize)]
+pub enum QuantizationGranularity {
+    /// One scale for the whole weights matrix.
+    #[default]
+    PerLayer,
+    /// One scale per neuron (row of the weights matrix). More accurate when neurons have
+    /// very different weight ranges.
+    PerRow,
+}
+
+/// Symmetric int8 scale for values whose largest magnitude is `max_abs`.
+fn symmetric_scale(max_abs: f64) -> f64 {
+    if max_abs > 0.0 {
+        max_abs / i8::MAX as f64
+    } else {
+        1.0
+    }
+}
+
+fn quantize_value(value: f64, scale: f64) -> i8 {
+    (value / scale)
+        .round()
+        .clamp(-(i8::MAX as f64), i8::MAX as f64) as i8
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct QuantizedLayer {
+    neurons: usize,
+    inputs: usize,
+    /// Row-major `neurons x inputs` weights.
+    weights: Vec<i8>,
+    /// A single scale, or one per neuron.
+    scales: Vec<f64>,
+    bias: Vec<f64>,
+}
+
+impl QuantizedLayer {
+    fn from_layer(layer: &Layer, granularity: QuantizationGranularity) -> Self {
+        let (neurons, inputs) = layer.shape();
+        let weights = layer.weights();
+
+        let scales = match granularity {
+            QuantizationGranularity::PerLayer => vec![symmetric_scale(weights.amax())],
+            QuantizationGranularity::PerRow => (0..neurons)
+                .map(|row| symmetric_scale(weights.row(row).amax()))
+                .collect(),
+        };
+
+        let mut layer = Self {
+            neurons,
+            inputs,
+            weights: Vec::with_capacity(neurons * inputs),
+            scales,
+            bias: layer.biases().iter().copied().collect(),
+        };
+        for row in 0..neurons {
+            let scale = layer.scale(row, granularity);
+            for col in 0..inputs {
+                layer
+                    .weights
+                    .push(quantize_value(weights[(row, col)], scale));
+            }
+        }
+        layer
+    }
+
+    fn scale(&self, row: usize, granularity: QuantizationGranularity) -> f64 {
+        match granularity {
+            QuantizationGranularity::PerLayer => self.scales[0],
+            QuantizationGranularity::PerRow => self.scales[row],
+        }
+    }
+
+    /// Checks that the stored vectors have the lengths the shape and granularity call for,
+    /// since deserialized data is not validated by serde.
+    fn check(&self, granularity: QuantizationGranularity) -> Result<(), String> {
+        let expected_scales = match granularity {
+            QuantizationGranularity::PerLayer => 1,
+            QuantizationGranularity::PerRow => self.neurons,
+        };
+        if self.weights.len() != self.neurons * self.inputs {
+            return Err(format!(
+                "expected {}x{} weights, got {}",
+                self.neurons,
+                self.inputs,
+                self.weights.len()
+            ));
+        }
+        if self.scales.len() != expected_scales {
+            return Err(format!(
+                "expected {} scales for {:?} quantization, got {}",
+                expected_scales,
+                granularity,
+                self.scales.len()
+            ));
+        }
+        if self.bias.len() != self.neurons {
+            return Err(format!(
+                "expected {} biases, got {}",
+                self.neurons,
+                self.bias.len()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Quantizes the inputs with a per-call scale, accumulates in `i32` and dequantizes
+    /// before adding the bias and applying the activation.
+    fn forward(
+        &self,
+        inputs: &[f64],
+        granularity: QuantizationGranularity,
+        activation_func: fn(f64) -> f64,
+    ) -> Vec<f64> {
+        let input_scale = symmetric_scale(inputs.iter().fold(0.0, |acc: f64, x| acc.max(x.abs())));
+        let quantized_inputs: Vec<i8> = inputs
+            .iter()
+            .map(|&x| quantize_value(x, input_scale))
+            .collect();
+
+        (0..self.neurons)
+            .map(|row| {
+                let weights = &self.weights[row * self.inputs..(row + 1) * self.inputs];
+                let accumulator: i32 = weights
+                    .iter()
+                    .zip(&quantized_inputs)
+                    .map(|(&w, &x)| w as i32 * x as i32)
+                    .sum();
+                let value = accumulator as f64 * self.scale(row, granularity) * input_scale
+                    + self.bias[row];
+                activation_func(value)
+            })
+            .collect()
+    }
+}
+
+/// Post-training int8 quantized copy of a [`NeuralNetwork`].
+///
+/// Weights are stored as symmetric int8 values with per-layer or per-row scale factors.
+/// Biases stay in `f64`, since they are few and dominate the error when quantized.
+///
+/// # Example
+///
+/// ```
+/// use only_brain::{NeuralNetwork, QuantizationGranularity};
+///
+/// let nn = NeuralNetwork::new(&vec![4, 8, 2]);
+/// let quantized = nn.quantize(QuantizationGranularity::PerRow);
+///
+/// let calibration = vec![vec![0.1, 0.2, 0.3, 0.4], vec![-1.0, 0.5, 0.0, 1.0]];
+/// let error = quantized.max_error(&nn, &calibration);
+/// assert!(error < 0.05);
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QuantizedNetwork {
+    layers: Vec<QuantizedLayer>,
+    activation_function: ActivationFunction,
+    granularity: QuantizationGranularity,
+    input_transform: Option<Scaler>,
+}
+
+impl QuantizedNetwork {
+    /// Quantizes the weights of `network` with the given granularity.
+    pub fn from_network(network: &NeuralNetwork, granularity: QuantizationGranularity) -> Self {
+        Self {
+            layers: network
+                .layers()
+                .iter()
+                .map(|layer| QuantizedLayer::from_layer(layer, granularity))
+                .collect(),
+            activation_function: network.activation_function(),
+            granularity,
+            input_transform: network.input_transform().cloned(),
+        }
+    }
+
+    /// Feeds the given inputs to the quantized network and returns the dequantized output.
+    /// The inputs must have the same size as the first layer of the network. The input
+    /// transform of the original network, if any, is applied first.
+    pub fn feed_forward(&self, inputs: &[f64]) -> Vec<f64> {
+        assert_eq!(
+            inputs.len(),
+            self.layer_size(0),
+            "expected {} inputs, got {}",
+            self.layer_size(0),
+            inputs.len()
+        );
+        let activation = get_activation_function(self.activation_function);
+
+        let mut outputs = match &self.input_transform {
+            Some(scaler) => scaler.transform(inputs),
+            None => inputs.to_vec(),
+        };
+        for layer in &self.layers {
+            outputs = layer.forward(&outputs, self.granularity, activation);
+        }
+        outputs
+    }
+
+    /// Checks that every layer is consistent with its shape and the granularity, and that
+    /// each layer takes as many inputs as the previous one has neurons. Loading a model
+    /// runs this, so a corrupted file is an error instead of a panic in `feed_forward`.
+    pub(crate) fn check(&self) -> Result<(), String> {
+        if self.layers.is_empty() {
+            return Err("the model has no layers".to_string());
+        }
+        for (index, layer) in self.layers.iter().enumerate() {
+            layer
+                .check(self.granularity)
+                .map_err(|error| format!("layer {}: {}", index + 1, error))?;
+        }
+        for (index, pair) in self.layers.windows(2).enumerate() {
+            if pair[1].inputs != pair[0].neurons {
+                return Err(format!(
+                    "layer {} takes {} inputs but layer {} has {} neurons",
+                    index + 2,
+                    pair[1].inputs,
+                    index + 1,
+                    pair[0].neurons
+                ));
+            }
+        }
+        match &self.input_transform {
+            Some(scaler) if scaler.len() != self.layers[0].inputs => Err(format!(
+                "the network takes {} inputs but the scaler was fitted on {} features",
+                self.layers[0].inputs,
+                scaler.len()
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the largest absolute difference between the outputs of this network and
+    /// `reference` over the calibration samples.
+    pub fn max_error(&self, reference: &NeuralNetwork, calibration: &[Vec<f64>]) -> f64 {
+        calibration
+            .iter()
+            .flat_map(|sample| {
+                let expected = reference.feed_forward(sample);
+                let actual = self.feed_forward(sample);
+                expected
+                    .into_iter()
+                    .zip(actual)
+                    .map(|(e, a)| (e - a).abs())
+                    .collect::<Vec<f64>>()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    /// Returns the number of layers of the network, including the input layer.
+    pub fn num_layers(&self) -> usize {
+        self.layers.len() + 1
+    }
+
+    /// Returns the number of neurons of the given layer.
+    pub fn layer_size(&self, layer: usize) -> usize {
+        if layer == 0 {
+            return self.layers[0].inputs;
+        }
+        self.layers[layer - 1].neurons
+    }
+
+    pub fn activation_function(&self) -> ActivationFunction {
+        self.activation_function
+    }
+
+    pub fn granularity(&self) -> QuantizationGranularity {
+        self.granularity
+    }
+}
+
+impl NeuralNetwork {
+    /// Returns an int8 quantized copy of this network. See [`QuantizedNetwork`].
+    pub fn quantize(&self, granularity: QuantizationGranularity) -> QuantizedNetwork {
+        QuantizedNetwork::from_network(self, granularity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{dmatrix, dvector};
+
+    fn network() -> NeuralNetwork {
+        let mut nn = NeuralNetwork::new(&vec![2, 2, 1]);
+        nn.set_layer_weights(1, dmatrix![0.5, -0.25; 0.01, 0.02]);
+        nn.set_layer_biases(1, dvector![0.1, -0.1]);
+        nn.set_layer_weights(2, dmatrix![1.5, -2.0]);
+        nn.set_layer_biases(2, dvector![0.3]);
+        nn
+    }
+
+    #[test]
+    fn weights_are_scaled_into_the_int8_range() {
+        let quantized = network().quantize(QuantizationGranularity::PerLayer);
+        let layer = &quantized.layers[0];
+
+        assert_eq!(layer.scales, vec![0.5 / 127.0]);
+        assert_eq!(layer.weights[0], 127);
+        assert!((layer.weights[1] as f64 * layer.scales[0] + 0.25).abs() <= layer.scales[0] / 2.0);
+    }
+
+    #[test]
+    fn per_row_scales_keep_small_rows_precise() {
+        let quantized = network().quantize(QuantizationGranularity::PerRow);
+        let layer = &quantized.layers[0];
+
+        assert_eq!(layer.scales.len(), 2);
+        assert_eq!(layer.weights[3], 127);
+    }
+
+    #[test]
+    fn quantized_outputs_stay_close_to_the_float_network() {
+        let nn = network();
+        let calibration = vec![vec![1.0, 2.0], vec![-0.5, 0.25], vec![0.0, 0.0]];
+
+        for granularity in [
+            QuantizationGranularity::PerLayer,
+            QuantizationGranularity::PerRow,
+        ] {
+            let error = nn.quantize(granularity).max_error(&nn, &calibration);
+            assert!(error < 0.01, "{granularity:?} error too large: {error}");
+        }
+    }
+
+    #[test]
+    fn inconsistent_layers_fail_the_check() {
+        let valid = network().quantize(QuantizationGranularity::PerRow);
+        assert_eq!(valid.check(), Ok(()));
+
+        let mut short_weights = valid.clone();
+        short_weights.layers[0].weights.pop();
+        assert_eq!(
+            short_weights.check(),
+            Err("layer 1: expected 2x2 weights, got 3".to_string())
+        );
+
+        let mut one_scale = valid.clone();
+        one_scale.layers[1].scales.clear();
+        assert_eq!(
+            one_scale.check(),
+            Err("layer 2: expected 1 scales for PerRow quantization, got 0".to_string())
+        );
+
+        let mut per_layer = valid;
+        per_layer.granularity = QuantizationGranularity::PerLayer;
+        assert_eq!(
+            per_layer.check(),
+            Err("layer 1: expected 1 scales for PerLayer quantization, got 2".to_string())
+        );
+    }
+
+    #[test]
+    fn all_zero_weights_do_not_divide_by_zero() {
+        let mut nn = NeuralNetwork::new(&vec![2, 1]);
+        nn.set_layer_weights(1, dmatrix![0.0, 0.0]);
+        nn.set_layer_biases(1, dvector![0.0]);
+
+        let output = nn
+            .quantize(QuantizationGranularity::PerRow)
+            .feed_forward(&[0.0, 0.0]);
+
+        assert_eq!(output, vec![0.5]);
+    }
+}
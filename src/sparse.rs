@@ -0,0 +1,448 @@
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+use crate::activation_functions::{get_activation_function, ActivationFunction};
+use crate::layer::Layer;
+use crate::preprocessing::Scaler;
+use crate::NeuralNetwork;
+
+/// Compressed sparse row matrix. Only the non-zero entries are stored.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CsrMatrix {
+    nrows: usize,
+    ncols: usize,
+    /// `row_offsets[r]..row_offsets[r + 1]` indexes the entries of row `r`.
+    row_offsets: Vec<u32>,
+    col_indices: Vec<u32>,
+    values: Vec<f64>,
+}
+
+impl CsrMatrix {
+    pub fn from_dense(matrix: &DMatrix<f64>) -> Self {
+        let mut row_offsets = Vec::with_capacity(matrix.nrows() + 1);
+        let mut col_indices = Vec::new();
+        let mut values = Vec::new();
+
+        row_offsets.push(0);
+        for row in 0..matrix.nrows() {
+            for col in 0..matrix.ncols() {
+                let value = matrix[(row, col)];
+                if value != 0.0 {
+                    col_indices.push(col as u32);
+                    values.push(value);
+                }
+            }
+            row_offsets.push(values.len() as u32);
+        }
+
+        Self {
+            nrows: matrix.nrows(),
+            ncols: matrix.ncols(),
+            row_offsets,
+            col_indices,
+            values,
+        }
+    }
+
+    pub fn to_dense(&self) -> DMatrix<f64> {
+        let mut matrix = DMatrix::zeros(self.nrows, self.ncols);
+        for row in 0..self.nrows {
+            for entry in self.row_range(row) {
+                matrix[(row, self.col_indices[entry] as usize)] = self.values[entry];
+            }
+        }
+        matrix
+    }
+
+    fn row_range(&self, row: usize) -> std::ops::Range<usize> {
+        self.row_offsets[row] as usize..self.row_offsets[row + 1] as usize
+    }
+
+    /// Sparse matrix-vector product.
+    pub fn mul_vector(&self, vector: &DVector<f64>) -> DVector<f64> {
+        assert_eq!(vector.len(), self.ncols, "Incompatible vector size");
+
+        DVector::from_fn(self.nrows, |row, _| {
+            self.row_range(row)
+                .map(|entry| self.values[entry] * vector[self.col_indices[entry] as usize])
+                .sum()
+        })
+    }
+
+    /// Returns the `(rows, columns)` shape of the matrix.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.nrows, self.ncols)
+    }
+
+    /// Number of stored (non-zero) entries.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Fraction of the entries that are zero.
+    pub fn sparsity(&self) -> f64 {
+        1.0 - self.nnz() as f64 / (self.nrows * self.ncols) as f64
+    }
+
+    /// Checks that the row offsets and column indices stay within the stored entries and
+    /// the shape, since deserialized data is not validated by serde.
+    fn check(&self) -> Result<(), String> {
+        if self.row_offsets.len() != self.nrows + 1 {
+            return Err(format!(
+                "expected {} row offsets for {} rows, got {}",
+                self.nrows + 1,
+                self.nrows,
+                self.row_offsets.len()
+            ));
+        }
+        if self.row_offsets[0] != 0 || self.row_offsets.windows(2).any(|pair| pair[0] > pair[1]) {
+            return Err("the row offsets must start at 0 and never decrease".to_string());
+        }
+        if self.row_offsets[self.nrows] as usize != self.values.len() {
+            return Err(format!(
+                "the row offsets end at {} but there are {} values",
+                self.row_offsets[self.nrows],
+                self.values.len()
+            ));
+        }
+        if self.col_indices.len() != self.values.len() {
+            return Err(format!(
+                "expected {} column indices, got {}",
+                self.values.len(),
+                self.col_indices.len()
+            ));
+        }
+        match self
+            .col_indices
+            .iter()
+            .find(|&&col| col as usize >= self.ncols)
+        {
+            Some(col) => Err(format!(
+                "column index {} is out of range for {} columns",
+                col, self.ncols
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A [`Layer`] whose weights are stored in compressed sparse row form.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SparseLayer {
+    weights: CsrMatrix,
+    bias: DVector<f64>,
+}
+
+impl SparseLayer {
+    pub fn forward(&self, inputs: &DVector<f64>, activation_func: fn(f64) -> f64) -> DVector<f64> {
+        let outputs = self.weights.mul_vector(inputs) + &self.bias;
+        outputs.map(activation_func)
+    }
+
+    pub fn size(&self) -> usize {
+        self.bias.len()
+    }
+
+    pub fn weights(&self) -> &CsrMatrix {
+        &self.weights
+    }
+
+    pub fn biases(&self) -> &DVector<f64> {
+        &self.bias
+    }
+
+    fn check(&self) -> Result<(), String> {
+        self.weights.check()?;
+        if self.bias.len() != self.weights.nrows {
+            return Err(format!(
+                "expected {} biases, got {}",
+                self.weights.nrows,
+                self.bias.len()
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Layer {
+    pub fn to_sparse(&self) -> SparseLayer {
+        SparseLayer {
+            weights: CsrMatrix::from_dense(self.weights()),
+            bias: self.biases().clone(),
+        }
+    }
+}
+
+/// Size and sparsity of a network in dense and sparse form, as returned by
+/// [`NeuralNetwork::sparsity_report`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SparsityReport {
+    /// Fraction of the weights that are zero.
+    pub sparsity: f64,
+    /// Size in bytes of the model serialized by [`dump_model`](crate::dump_model).
+    pub dense_bytes: u64,
+    /// Size in bytes of the model serialized by
+    /// [`dump_sparse_model`](crate::dump_sparse_model).
+    pub sparse_bytes: u64,
+}
+
+impl SparsityReport {
+    /// Fraction of the dense serialized size saved by the sparse form. Negative when the
+    /// sparse form is larger, which happens below roughly one third sparsity.
+    pub fn size_reduction(&self) -> f64 {
+        1.0 - self.sparse_bytes as f64 / self.dense_bytes as f64
+    }
+}
+
+/// A [`NeuralNetwork`] with every layer in compressed sparse row form. Worth using once
+/// most weights are zero, typically after [`NeuralNetwork::prune`].
+///
+/// # Example
+///
+/// ```
+/// # use only_brain::{NeuralNetwork, PruneCriterion, PruneScope};
+/// let mut nn = NeuralNetwork::new(&vec![16, 16, 4]);
+/// nn.prune(PruneCriterion::Fraction(0.9), PruneScope::PerLayer);
+///
+/// let sparse = nn.to_sparse();
+/// let input = vec![0.5; 16];
+/// let dense_output = nn.feed_forward(&input);
+/// let sparse_output = sparse.feed_forward(&input);
+/// for (d, s) in dense_output.iter().zip(&sparse_output) {
+///     assert!((d - s).abs() < 1e-12);
+/// }
+///
+/// assert!(nn.sparsity_report().size_reduction() > 0.5);
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SparseNetwork {
+    layers: Vec<SparseLayer>,
+    activation_function: ActivationFunction,
+    input_transform: Option<Scaler>,
+}
+
+impl SparseNetwork {
+    pub fn from_network(network: &NeuralNetwork) -> Self {
+        Self {
+            layers: network.layers().iter().map(Layer::to_sparse).collect(),
+            activation_function: network.activation_function(),
+            input_transform: network.input_transform().cloned(),
+        }
+    }
+
+    /// Converts back to a dense [`NeuralNetwork`].
+    pub fn to_dense(&self) -> NeuralNetwork {
+        let layers = self
+            .layers
+            .iter()
+            .map(|layer| Layer::from_parts(layer.weights.to_dense(), layer.bias.clone()))
+            .collect();
+        let mut network = NeuralNetwork::from_layers(layers, self.activation_function);
+        if let Some(scaler) = &self.input_transform {
+            network.set_input_transform(scaler.clone());
+        }
+        network
+    }
+
+    /// Feeds the given inputs to the network using sparse matrix-vector products. The
+    /// input transform of the original network, if any, is applied first.
+    pub fn feed_forward(&self, inputs: &[f64]) -> Vec<f64> {
+        let mut outputs = match &self.input_transform {
+            Some(scaler) => DVector::from(scaler.transform(inputs)),
+            None => DVector::from_column_slice(inputs),
+        };
+        let activation = get_activation_function(self.activation_function);
+
+        for layer in &self.layers {
+            outputs = layer.forward(&outputs, activation);
+        }
+
+        outputs.data.into()
+    }
+
+    /// Checks that every layer is consistent with its shape, and that each layer takes
+    /// as many inputs as the previous one has neurons. Loading a model runs this, so a
+    /// corrupted file is an error instead of an out-of-bounds panic in `feed_forward`.
+    pub(crate) fn check(&self) -> Result<(), String> {
+        if self.layers.is_empty() {
+            return Err("the model has no layers".to_string());
+        }
+        for (index, layer) in self.layers.iter().enumerate() {
+            layer
+                .check()
+                .map_err(|error| format!("layer {}: {}", index + 1, error))?;
+        }
+        for (index, pair) in self.layers.windows(2).enumerate() {
+            if pair[1].weights.ncols != pair[0].size() {
+                return Err(format!(
+                    "layer {} takes {} inputs but layer {} has {} neurons",
+                    index + 2,
+                    pair[1].weights.ncols,
+                    index + 1,
+                    pair[0].size()
+                ));
+            }
+        }
+        match &self.input_transform {
+            Some(scaler) if scaler.len() != self.layer_size(0) => Err(format!(
+                "the network takes {} inputs but the scaler was fitted on {} features",
+                self.layer_size(0),
+                scaler.len()
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the number of layers of the network, including the input layer.
+    pub fn num_layers(&self) -> usize {
+        self.layers.len() + 1
+    }
+
+    /// Returns the number of neurons of the given layer.
+    pub fn layer_size(&self, layer: usize) -> usize {
+        if layer == 0 {
+            return self.layers[0].weights.shape().1;
+        }
+        self.layers[layer - 1].size()
+    }
+
+    pub fn layers(&self) -> &[SparseLayer] {
+        &self.layers
+    }
+
+    pub fn activation_function(&self) -> ActivationFunction {
+        self.activation_function
+    }
+
+    /// Fraction of all weights that are zero.
+    pub fn sparsity(&self) -> f64 {
+        let (zeros, total) = self.layers.iter().fold((0, 0), |(zeros, total), layer| {
+            let (rows, cols) = layer.weights.shape();
+            (
+                zeros + rows * cols - layer.weights.nnz(),
+                total + rows * cols,
+            )
+        });
+        zeros as f64 / total as f64
+    }
+}
+
+impl NeuralNetwork {
+    /// Returns a copy of this network with its weights in compressed sparse row form.
+    pub fn to_sparse(&self) -> SparseNetwork {
+        SparseNetwork::from_network(self)
+    }
+
+    /// Reports the weight sparsity and the serialized size in dense and sparse form.
+    pub fn sparsity_report(&self) -> SparsityReport {
+        let sparse = self.to_sparse();
+        // Bincode only fails on I/O and on types it cannot encode, neither of which
+        // applies to a network held in memory.
+        let dense =
+            crate::dump_model_to_bytes(self).expect("an in-memory network always serializes");
+
+        SparsityReport {
+            sparsity: sparse.sparsity(),
+            dense_bytes: dense.len() as u64,
+            sparse_bytes: bincode::serialized_size(&sparse)
+                .expect("an in-memory network always serializes"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{dmatrix, dvector};
+
+    #[test]
+    fn csr_round_trips_through_dense() {
+        let dense = dmatrix![0.0, 1.5, 0.0; -2.0, 0.0, 0.0];
+        let csr = CsrMatrix::from_dense(&dense);
+
+        assert_eq!(csr.nnz(), 2);
+        assert!((csr.sparsity() - 4.0 / 6.0).abs() < 1e-12);
+        assert_eq!(csr.to_dense(), dense);
+    }
+
+    #[test]
+    fn csr_matrix_vector_product_matches_dense() {
+        let dense = dmatrix![0.0, 1.5, 0.0; -2.0, 0.0, 0.5];
+        let vector = dvector![1.0, 2.0, 3.0];
+
+        assert_eq!(
+            CsrMatrix::from_dense(&dense).mul_vector(&vector),
+            &dense * &vector
+        );
+    }
+
+    #[test]
+    fn sparse_network_converts_back_to_an_equal_dense_network() {
+        let mut nn = NeuralNetwork::new(&vec![3, 2, 1]);
+        nn.set_activation_function(ActivationFunction::Tanh);
+        nn.set_layer_weights(1, dmatrix![0.0, 0.3, 0.0; 0.0, 0.0, -0.7]);
+
+        let sparse = nn.to_sparse();
+
+        assert_eq!(sparse.to_dense(), nn);
+        assert_eq!(
+            sparse.feed_forward(&[0.1, 0.2, 0.3]),
+            nn.feed_forward(&vec![0.1, 0.2, 0.3])
+        );
+    }
+
+    #[test]
+    fn a_mostly_zero_network_serializes_smaller_in_sparse_form() {
+        let mut nn = NeuralNetwork::new(&vec![10, 10]);
+        nn.set_layer_weights(1, DMatrix::identity(10, 10));
+
+        let report = nn.sparsity_report();
+
+        assert!((report.sparsity - 0.9).abs() < 1e-12);
+        assert!(report.sparse_bytes < report.dense_bytes);
+        assert!(report.size_reduction() > 0.5);
+    }
+
+    #[test]
+    fn inconsistent_layers_fail_the_check() {
+        let mut nn = NeuralNetwork::new(&vec![3, 2, 1]);
+        nn.set_layer_weights(1, dmatrix![0.0, 0.3, 0.0; 0.0, 0.0, -0.7]);
+        let valid = nn.to_sparse();
+        assert_eq!(valid.check(), Ok(()));
+
+        let mut short_offsets = valid.clone();
+        short_offsets.layers[0].weights.row_offsets.pop();
+        assert_eq!(
+            short_offsets.check(),
+            Err("layer 1: expected 3 row offsets for 2 rows, got 2".to_string())
+        );
+
+        let mut decreasing = valid.clone();
+        decreasing.layers[0].weights.row_offsets[1] = 3;
+        assert_eq!(
+            decreasing.check(),
+            Err("layer 1: the row offsets must start at 0 and never decrease".to_string())
+        );
+
+        let mut wide_column = valid.clone();
+        wide_column.layers[0].weights.col_indices[1] = 3;
+        assert_eq!(
+            wide_column.check(),
+            Err("layer 1: column index 3 is out of range for 3 columns".to_string())
+        );
+
+        let mut missing_value = valid.clone();
+        missing_value.layers[0].weights.values.pop();
+        assert_eq!(
+            missing_value.check(),
+            Err("layer 1: the row offsets end at 2 but there are 1 values".to_string())
+        );
+
+        let mut unchained = valid;
+        unchained.layers[1].weights.ncols = 3;
+        assert_eq!(
+            unchained.check(),
+            Err("layer 2 takes 3 inputs but layer 1 has 2 neurons".to_string())
+        );
+    }
+}
@@ -0,0 +1,276 @@
+use std::fmt;
+
+use crate::activation_functions::ActivationFunction;
+use crate::NeuralNetwork;
+
+/// Number of bins used by the histograms in [`ParameterStats`].
+pub const HISTOGRAM_BINS: usize = 10;
+
+/// One row of a [`NetworkSummary`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayerSummary {
+    pub layer: usize,
+    pub inputs: usize,
+    pub outputs: usize,
+    pub activation_function: ActivationFunction,
+    /// Weights plus biases.
+    pub parameters: usize,
+}
+
+/// Per-layer overview of a network, returned by [`NeuralNetwork::summary`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct NetworkSummary {
+    pub input_size: usize,
+    pub layers: Vec<LayerSummary>,
+}
+
+impl NetworkSummary {
+    pub fn total_parameters(&self) -> usize {
+        self.layers.iter().map(|layer| layer.parameters).sum()
+    }
+}
+
+impl fmt::Display for NetworkSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<6} {:>8} {:>8} {:<12} {:>10}",
+            "Layer", "Inputs", "Outputs", "Activation", "Params"
+        )?;
+        writeln!(f, "{}", "-".repeat(48))?;
+        for layer in &self.layers {
+            writeln!(
+                f,
+                "{:<6} {:>8} {:>8} {:<12} {:>10}",
+                layer.layer,
+                layer.inputs,
+                layer.outputs,
+                format!("{:?}", layer.activation_function),
+                layer.parameters
+            )?;
+        }
+        writeln!(f, "{}", "-".repeat(48))?;
+        writeln!(f, "Input size: {}", self.input_size)?;
+        writeln!(f, "Total params: {}", self.total_parameters())?;
+        Ok(())
+    }
+}
+
+/// Equal-width histogram between the smallest and largest value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    pub min: f64,
+    pub max: f64,
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    fn from_values(values: &[f64], min: f64, max: f64) -> Self {
+        let mut counts = vec![0; HISTOGRAM_BINS];
+        let width = (max - min) / HISTOGRAM_BINS as f64;
+
+        for &value in values {
+            let bin = if width > 0.0 {
+                (((value - min) / width) as usize).min(HISTOGRAM_BINS - 1)
+            } else {
+                0
+            };
+            counts[bin] += 1;
+        }
+
+        Self { min, max, counts }
+    }
+
+    /// Returns the `[start, end)` range covered by `bin`. The last bin also includes `max`.
+    pub fn bin_range(&self, bin: usize) -> (f64, f64) {
+        let width = (self.max - self.min) / self.counts.len() as f64;
+        (
+            self.min + width * bin as f64,
+            self.min + width * (bin + 1) as f64,
+        )
+    }
+}
+
+/// Descriptive statistics over a set of weights or biases.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParameterStats {
+    pub count: usize,
+    pub mean: f64,
+    /// Population standard deviation.
+    pub std: f64,
+    pub min: f64,
+    pub max: f64,
+    pub l2_norm: f64,
+    pub zero_fraction: f64,
+    pub histogram: Histogram,
+}
+
+impl ParameterStats {
+    fn from_values(values: &[f64]) -> Self {
+        let count = values.len();
+        let n = count as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        Self {
+            count,
+            mean,
+            std: variance.sqrt(),
+            min,
+            max,
+            l2_norm: values.iter().map(|v| v * v).sum::<f64>().sqrt(),
+            zero_fraction: values.iter().filter(|&&v| v == 0.0).count() as f64 / n,
+            histogram: Histogram::from_values(values, min, max),
+        }
+    }
+}
+
+impl fmt::Display for ParameterStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "count: {}  mean: {:.4}  std: {:.4}  min: {:.4}  max: {:.4}  l2: {:.4}  zeros: {:.1}%",
+            self.count,
+            self.mean,
+            self.std,
+            self.min,
+            self.max,
+            self.l2_norm,
+            self.zero_fraction * 100.0
+        )?;
+        let widest = self
+            .histogram
+            .counts
+            .iter()
+            .copied()
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        for (bin, &count) in self.histogram.counts.iter().enumerate() {
+            let (start, end) = self.histogram.bin_range(bin);
+            let bar = "#".repeat(count * 40 / widest);
+            writeln!(f, "  [{:>8.4}, {:>8.4}) {:>6} {}", start, end, count, bar)?;
+        }
+        Ok(())
+    }
+}
+
+/// Weight and bias statistics of a single layer, returned by [`NeuralNetwork::layer_stats`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayerStats {
+    pub layer: usize,
+    pub weights: ParameterStats,
+    pub biases: ParameterStats,
+}
+
+impl fmt::Display for LayerStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Layer {}", self.layer)?;
+        writeln!(f, "Weights: {}", self.weights)?;
+        write!(f, "Biases: {}", self.biases)
+    }
+}
+
+impl NeuralNetwork {
+    /// Returns a per-layer overview of the network: input and output sizes, activation
+    /// function and parameter count, plus totals when printed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use only_brain::NeuralNetwork;
+    /// let nn = NeuralNetwork::new(&vec![784, 128, 10]);
+    /// let summary = nn.summary();
+    ///
+    /// assert_eq!(summary.total_parameters(), 784 * 128 + 128 + 128 * 10 + 10);
+    /// println!("{}", summary);
+    /// ```
+    pub fn summary(&self) -> NetworkSummary {
+        let layers = self
+            .layers()
+            .iter()
+            .enumerate()
+            .map(|(index, layer)| {
+                let (outputs, inputs) = layer.shape();
+                LayerSummary {
+                    layer: index + 1,
+                    inputs,
+                    outputs,
+                    activation_function: self.activation_function(),
+                    parameters: outputs * inputs + outputs,
+                }
+            })
+            .collect();
+
+        NetworkSummary {
+            input_size: self.layer_size(0),
+            layers,
+        }
+    }
+
+    /// Returns statistics of the weights and biases of the given layer. The layer index
+    /// must be greater than 0 since the input layer does not have weights.
+    pub fn layer_stats(&self, layer: usize) -> LayerStats {
+        if layer == 0 {
+            panic!("Invalid layer index");
+        }
+        let target = &self.layers()[layer - 1];
+
+        LayerStats {
+            layer,
+            weights: ParameterStats::from_values(target.weights().as_slice()),
+            biases: ParameterStats::from_values(target.biases().as_slice()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{dmatrix, dvector};
+
+    const EPSILON: f64 = 1e-12;
+
+    #[test]
+    fn summary_counts_parameters_per_layer() {
+        let nn = NeuralNetwork::new(&vec![3, 5, 2]);
+        let summary = nn.summary();
+
+        assert_eq!(summary.input_size, 3);
+        assert_eq!(summary.layers.len(), 2);
+        assert_eq!(summary.layers[0].parameters, 3 * 5 + 5);
+        assert_eq!(summary.layers[1].inputs, 5);
+        assert_eq!(summary.layers[1].outputs, 2);
+        assert_eq!(summary.total_parameters(), 20 + 12);
+        assert!(summary.to_string().contains("Total params: 32"));
+    }
+
+    #[test]
+    fn layer_stats_describe_weights_and_biases() {
+        let mut nn = NeuralNetwork::new(&vec![2, 2]);
+        nn.set_layer_weights(1, dmatrix![0.0, 1.0; -1.0, 2.0]);
+        nn.set_layer_biases(1, dvector![0.0, 0.0]);
+
+        let stats = nn.layer_stats(1);
+
+        assert_eq!(stats.weights.count, 4);
+        assert!((stats.weights.mean - 0.5).abs() < EPSILON);
+        assert!((stats.weights.std - 1.25f64.sqrt()).abs() < EPSILON);
+        assert_eq!(stats.weights.min, -1.0);
+        assert_eq!(stats.weights.max, 2.0);
+        assert!((stats.weights.l2_norm - 6f64.sqrt()).abs() < EPSILON);
+        assert_eq!(stats.weights.zero_fraction, 0.25);
+        assert_eq!(stats.weights.histogram.counts.iter().sum::<usize>(), 4);
+        assert_eq!(stats.weights.histogram.counts[HISTOGRAM_BINS - 1], 1);
+        assert_eq!(stats.biases.zero_fraction, 1.0);
+        assert_eq!(stats.biases.histogram.counts[0], 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid layer index")]
+    fn layer_stats_rejects_layer_zero() {
+        NeuralNetwork::new(&vec![2, 1]).layer_stats(0);
+    }
+}
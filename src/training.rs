@@ -0,0 +1,290 @@
+use nalgebra::RealField;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::{BVector, Perceptron};
+
+/// Settings for [`Perceptron::train_with`]. The learning rate has the element type of the
+/// model being trained, `f64` unless the perceptron is generic over another float.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrainOptions<T = f64> {
+    /// Learning rate.
+    pub eta: T,
+    /// Maximum number of passes over the samples.
+    pub epochs: usize,
+    /// Visits the samples in a new random order every epoch, reproducible from the seed.
+    /// `None` keeps the order they were given in.
+    pub shuffle_seed: Option<u64>,
+    /// Stops after the first epoch without misclassifications.
+    pub stop_on_convergence: bool,
+}
+
+impl<T: RealField> Default for TrainOptions<T> {
+    fn default() -> Self {
+        Self {
+            eta: nalgebra::convert(0.1),
+            epochs: 100,
+            shuffle_seed: None,
+            stop_on_convergence: true,
+        }
+    }
+}
+
+/// What happened while training, one entry per epoch run.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct TrainingHistory {
+    /// Number of misclassified samples in each epoch.
+    pub misclassifications: Vec<usize>,
+    /// `true` if the last epoch had no misclassifications.
+    pub converged: bool,
+}
+
+impl TrainingHistory {
+    pub fn epochs(&self) -> usize {
+        self.misclassifications.len()
+    }
+}
+
+/// Runs `epochs` passes of `step` over the sample indices, in the order the options ask
+/// for. `step` returns whether the sample was misclassified.
+pub(crate) fn run_epochs<T>(
+    samples: usize,
+    options: &TrainOptions<T>,
+    mut step: impl FnMut(usize) -> bool,
+) -> TrainingHistory {
+    let mut rng = options.shuffle_seed.map(StdRng::seed_from_u64);
+    let mut order: Vec<usize> = (0..samples).collect();
+    let mut history = TrainingHistory::default();
+
+    for _ in 0..options.epochs {
+        if let Some(rng) = rng.as_mut() {
+            order.shuffle(rng);
+        }
+        let misclassified = order.iter().filter(|&&index| step(index)).count();
+        history.misclassifications.push(misclassified);
+        history.converged = misclassified == 0;
+
+        if history.converged && options.stop_on_convergence {
+            break;
+        }
+    }
+    history
+}
+
+pub(crate) fn check_labels(samples: usize, labels: usize) {
+    assert_eq!(
+        samples, labels,
+        "got {} samples but {} labels",
+        samples, labels
+    );
+}
+
+impl<T: RealField + Copy, const N: usize> Perceptron<T, N> {
+    /// Trains the perceptron with the classic error-driven rule
+    /// `w += eta * (target - output) * x`, and the same for the bias, until an epoch has
+    /// no misclassifications or `epochs` have run.
+    ///
+    /// Labels are the classes in the perceptron's element type, `0.0` or `1.0`. The output is
+    /// read as class 1 when it is at least 0.5, as in
+    /// [`evaluate_classification`](Self::evaluate_classification), so training also
+    /// converges with a continuous activation such as
+    /// [`ActivationFunction::Sigmoid`](crate::ActivationFunction::Sigmoid). A sample counts
+    /// as misclassified when that class differs from its label.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is not one label per sample.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use only_brain::{bvector, ActivationFunction, Perceptron};
+    /// // Logical AND.
+    /// let samples = [bvector![0.0, 0.0], bvector![0.0, 1.0], bvector![1.0, 0.0], bvector![1.0, 1.0]];
+    /// let labels = [0.0, 0.0, 0.0, 1.0];
+    ///
+    /// let mut perceptron = Perceptron::<f64, 2>::new(ActivationFunction::BinaryStep);
+    /// let history = perceptron.train(&samples, &labels, 0.1, 50);
+    ///
+    /// assert!(history.converged);
+    /// assert_eq!(perceptron.feed_forward(&bvector![1.0, 1.0]), 1.0);
+    /// ```
+    pub fn train(
+        &mut self,
+        samples: &[BVector<T, N>],
+        labels: &[T],
+        eta: T,
+        epochs: usize,
+    ) -> TrainingHistory {
+        self.train_with(
+            samples,
+            labels,
+            &TrainOptions {
+                eta,
+                epochs,
+                ..TrainOptions::default()
+            },
+        )
+    }
+
+    /// Trains the perceptron like [`train`](Self::train), with the given options.
+    pub fn train_with(
+        &mut self,
+        samples: &[BVector<T, N>],
+        labels: &[T],
+        options: &TrainOptions<T>,
+    ) -> TrainingHistory {
+        check_labels(samples.len(), labels.len());
+        let threshold = nalgebra::convert::<f64, T>(0.5);
+
+        run_epochs(samples.len(), options, |index| {
+            let (sample, target) = (&samples[index], labels[index]);
+            let class = if self.feed_forward(sample) >= threshold {
+                T::one()
+            } else {
+                T::zero()
+            };
+            let error = target - class;
+            if error == T::zero() {
+                return false;
+            }
+            let step = options.eta * error;
+            self.set_weights(self.weights().clone() + sample.clone() * step);
+            self.set_bias(self.bias() + step);
+            true
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bvector, ActivationFunction};
+
+    fn or_gate() -> ([BVector<f64, 2>; 4], [f64; 4]) {
+        (
+            [
+                bvector![0.0, 0.0],
+                bvector![0.0, 1.0],
+                bvector![1.0, 0.0],
+                bvector![1.0, 1.0],
+            ],
+            [0.0, 1.0, 1.0, 1.0],
+        )
+    }
+
+    #[test]
+    fn train_learns_a_linearly_separable_function() {
+        let (samples, labels) = or_gate();
+        let mut perceptron = Perceptron::<f64, 2>::new(ActivationFunction::BinaryStep);
+        perceptron.set_bias(-0.05);
+
+        let history = perceptron.train(&samples, &labels, 0.1, 100);
+
+        assert!(history.converged);
+        assert_eq!(history.misclassifications.last(), Some(&0));
+        for (sample, label) in samples.iter().zip(labels) {
+            assert_eq!(perceptron.feed_forward(sample), label);
+        }
+    }
+
+    #[test]
+    fn single_precision_perceptrons_train_too() {
+        let samples: [BVector<f32, 2>; 4] = [
+            bvector![0.0, 0.0],
+            bvector![0.0, 1.0],
+            bvector![1.0, 0.0],
+            bvector![1.0, 1.0],
+        ];
+        let labels = [0.0, 1.0, 1.0, 1.0];
+        let mut perceptron = Perceptron::<f32, 2>::new(ActivationFunction::BinaryStep);
+        perceptron.set_bias(-0.05);
+
+        let history = perceptron.train(&samples, &labels, 0.1, 100);
+
+        assert!(history.converged);
+        for (sample, label) in samples.iter().zip(labels) {
+            assert_eq!(perceptron.feed_forward(sample), label);
+        }
+    }
+
+    /// Sigmoid outputs are never exactly 0 or 1, so comparing them with the labels as they
+    /// are would never converge.
+    #[test]
+    fn continuous_activations_converge_on_the_thresholded_output() {
+        let (samples, labels) = or_gate();
+        let mut perceptron = Perceptron::<f64, 2>::new(ActivationFunction::Sigmoid);
+
+        let history = perceptron.train(&samples, &labels, 0.5, 100);
+
+        assert!(history.converged);
+        for (sample, label) in samples.iter().zip(labels) {
+            assert_eq!(perceptron.feed_forward(sample) >= 0.5, label == 1.0);
+        }
+    }
+
+    /// The hand-written rule in the Iris example only ever added to the weights, so
+    /// class-0 samples could not push them down.
+    #[test]
+    fn misclassified_negatives_decrease_the_weights() {
+        let mut perceptron = Perceptron::<f64, 1>::new(ActivationFunction::BinaryStep);
+        perceptron.set_weights(bvector![1.0]);
+
+        perceptron.train(&[bvector![2.0]], &[0.0], 0.5, 1);
+
+        assert_eq!(perceptron.weights(), &bvector![0.0]);
+        assert_eq!(perceptron.bias(), -0.5);
+    }
+
+    #[test]
+    fn history_records_every_epoch_of_a_non_separable_problem() {
+        // XOR cannot be learned by a single perceptron.
+        let samples = [
+            bvector![0.0, 0.0],
+            bvector![0.0, 1.0],
+            bvector![1.0, 0.0],
+            bvector![1.0, 1.0],
+        ];
+        let labels = [0.0, 1.0, 1.0, 0.0];
+        let mut perceptron = Perceptron::<f64, 2>::new(ActivationFunction::BinaryStep);
+
+        let history = perceptron.train(&samples, &labels, 0.1, 25);
+
+        assert_eq!(history.epochs(), 25);
+        assert!(!history.converged);
+        assert!(history.misclassifications.iter().all(|&count| count > 0));
+    }
+
+    #[test]
+    fn shuffled_training_is_reproducible() {
+        let (samples, labels) = or_gate();
+        let options = TrainOptions {
+            shuffle_seed: Some(3),
+            stop_on_convergence: false,
+            epochs: 10,
+            ..TrainOptions::default()
+        };
+        let mut a = Perceptron::<f64, 2>::new(ActivationFunction::BinaryStep);
+        let mut b = Perceptron::<f64, 2>::new(ActivationFunction::BinaryStep);
+
+        let history = a.train_with(&samples, &labels, &options);
+        b.train_with(&samples, &labels, &options);
+
+        assert_eq!(history.epochs(), 10);
+        assert_eq!(a.weights(), b.weights());
+        assert_eq!(a.bias(), b.bias());
+    }
+
+    #[test]
+    #[should_panic(expected = "got 4 samples but 3 labels")]
+    fn train_rejects_missing_labels() {
+        let (samples, _) = or_gate();
+        Perceptron::<f64, 2>::new(ActivationFunction::BinaryStep).train(
+            &samples,
+            &[0.0, 1.0, 1.0],
+            0.1,
+            1,
+        );
+    }
+}
@@ -0,0 +1,76 @@
+/*
+ * Exercises the C API against a model dumped by the Rust test in tests/ffi.rs.
+ *
+ * Usage: ffi_test <model path>
+ * Prints the outputs for the input {0.5, 0.2}, one per line, and exits non-zero on the
+ * first failed check.
+ */
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "only_brain.h"
+
+#define CHECK(condition)                                                        \
+    do {                                                                        \
+        if (!(condition)) {                                                     \
+            const char *error = ob_last_error_message();                        \
+            fprintf(stderr, "%s:%d: check failed: %s (last error: %s)\n",       \
+                    __FILE__, __LINE__, #condition, error ? error : "none");    \
+            return 1;                                                           \
+        }                                                                       \
+    } while (0)
+
+static int read_file(const char *path, unsigned char **data, size_t *len) {
+    FILE *file = fopen(path, "rb");
+    if (!file) {
+        return 0;
+    }
+    fseek(file, 0, SEEK_END);
+    *len = (size_t)ftell(file);
+    fseek(file, 0, SEEK_SET);
+    *data = malloc(*len);
+    size_t read = fread(*data, 1, *len, file);
+    fclose(file);
+    return read == *len;
+}
+
+int main(int argc, char **argv) {
+    CHECK(argc == 2);
+
+    ObModel *model = NULL;
+    CHECK(ob_load_model(argv[1], &model) == OB_STATUS_OK);
+    CHECK(model != NULL);
+    CHECK(ob_num_layers(model) == 3);
+    CHECK(ob_layer_size(model, 0) == 2);
+    CHECK(ob_layer_size(model, 2) == 2);
+    CHECK(ob_layer_size(model, 3) == 0);
+
+    double inputs[2] = {0.5, 0.2};
+    double outputs[2] = {0.0, 0.0};
+    CHECK(ob_feed_forward(model, inputs, 2, outputs, 2) == OB_STATUS_OK);
+
+    double too_small[1];
+    CHECK(ob_feed_forward(model, inputs, 2, too_small, 1) == OB_STATUS_SIZE_MISMATCH);
+    CHECK(strstr(ob_last_error_message(), "produces 2 outputs") != NULL);
+    ob_free_model(model);
+
+    unsigned char *data = NULL;
+    size_t len = 0;
+    CHECK(read_file(argv[1], &data, &len));
+    ObModel *from_buffer = NULL;
+    CHECK(ob_load_model_from_buffer(data, len, &from_buffer) == OB_STATUS_OK);
+    double buffer_outputs[2] = {0.0, 0.0};
+    CHECK(ob_feed_forward(from_buffer, inputs, 2, buffer_outputs, 2) == OB_STATUS_OK);
+    CHECK(memcmp(outputs, buffer_outputs, sizeof outputs) == 0);
+    ob_free_model(from_buffer);
+    free(data);
+
+    ObModel *missing = NULL;
+    CHECK(ob_load_model("/nonexistent/only-brain-model.bin", &missing) == OB_STATUS_IO);
+    CHECK(missing == NULL);
+    CHECK(ob_load_model(NULL, &missing) == OB_STATUS_NULL_POINTER);
+
+    printf("%.17g\n%.17g\n", outputs[0], outputs[1]);
+    return 0;
+}
@@ -0,0 +1,212 @@
+//! Runs the `only-brain` binary. Needs `--features cli`.
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+use nalgebra::{dmatrix, dvector};
+use only_brain::{dump_model, load_model, NeuralNetwork};
+
+fn work_dir(name: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"))
+        .join("cli")
+        .join(name);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn only_brain(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_only-brain"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("binary should start");
+    // A command that fails before reading its input closes the pipe, which is not an
+    // error for these tests.
+    let _ = child.stdin.take().unwrap().write_all(stdin.as_bytes());
+    child.wait_with_output().unwrap()
+}
+
+fn dumped_network(dir: &std::path::Path) -> (NeuralNetwork, String) {
+    let mut nn = NeuralNetwork::new(&vec![2, 1]);
+    nn.set_layer_weights(1, dmatrix![0.5, -0.25]);
+    nn.set_layer_biases(1, dvector![0.1]);
+
+    let path = dir.join("model.bin").to_str().unwrap().to_string();
+    dump_model(&nn, &path).unwrap();
+    (nn, path)
+}
+
+#[test]
+fn info_prints_the_summary() {
+    let dir = work_dir("info");
+    let (_, model) = dumped_network(&dir);
+
+    let output = only_brain(&["info", &model], "");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("Activation Function: Sigmoid"), "{stdout}");
+    assert!(stdout.contains("Total params: 3"), "{stdout}");
+}
+
+#[test]
+fn convert_round_trips_through_json() {
+    let dir = work_dir("convert");
+    let (nn, model) = dumped_network(&dir);
+    let json = dir.join("model.json");
+    let back = dir.join("back.bin");
+
+    assert!(only_brain(&["convert", &model, json.to_str().unwrap()], "")
+        .status
+        .success());
+    assert!(std::fs::read_to_string(&json)
+        .unwrap()
+        .contains("\"layers\""));
+    assert!(only_brain(
+        &["convert", json.to_str().unwrap(), back.to_str().unwrap()],
+        ""
+    )
+    .status
+    .success());
+
+    assert_eq!(load_model(back.to_str().unwrap()).unwrap(), nn);
+}
+
+#[test]
+fn predict_reads_csv_from_stdin_and_skips_the_header() {
+    let dir = work_dir("predict");
+    let (nn, model) = dumped_network(&dir);
+
+    let output = only_brain(&["predict", &model], "a,b\n1.0,2.0\n0.5,0.2\n");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(lines[0], "output_0");
+    assert_eq!(lines[1], nn.feed_forward(&vec![1.0, 2.0])[0].to_string());
+    assert_eq!(lines[2], nn.feed_forward(&vec![0.5, 0.2])[0].to_string());
+}
+
+#[test]
+fn predict_rejects_rows_of_the_wrong_width() {
+    let dir = work_dir("predict-width");
+    let (_, model) = dumped_network(&dir);
+
+    let output = only_brain(&["predict", &model], "1.0,2.0,3.0\n");
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("the model takes 2"));
+}
+
+#[test]
+fn diff_fails_beyond_epsilon() {
+    let dir = work_dir("diff");
+    let (mut nn, model) = dumped_network(&dir);
+    nn.set_weight(1, 0, 0, 0.6);
+    let other = dir.join("other.bin").to_str().unwrap().to_string();
+    dump_model(&nn, &other).unwrap();
+
+    let loose = only_brain(&["diff", &model, &other, "--epsilon", "0.5"], "");
+    let strict = only_brain(&["diff", &model, &other, "--epsilon", "0.01"], "");
+
+    assert!(loose.status.success());
+    assert!(String::from_utf8_lossy(&loose.stdout).contains("Max |dW|"));
+    assert_eq!(strict.status.code(), Some(1));
+}
+
+#[test]
+fn validate_reports_non_finite_weights() {
+    let dir = work_dir("validate");
+    let (mut nn, model) = dumped_network(&dir);
+    nn.set_weight(1, 0, 1, f64::NAN);
+    let broken = dir.join("broken.bin").to_str().unwrap().to_string();
+    dump_model(&nn, &broken).unwrap();
+
+    assert!(only_brain(&["validate", &model], "").status.success());
+
+    let output = only_brain(&["validate", &broken], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("non-finite"));
+}
+
+#[test]
+fn models_without_layers_are_reported_instead_of_panicking() {
+    let dir = work_dir("no-layers");
+    let empty = dir.join("empty.json");
+    std::fs::write(&empty, r#"{"layers": [], "activation_function": null}"#).unwrap();
+    let empty = empty.to_str().unwrap();
+
+    let validate = only_brain(&["validate", empty], "");
+    let info = only_brain(&["info", empty], "");
+    let predict = only_brain(&["predict", empty], "1.0\n");
+
+    assert_eq!(validate.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&validate.stderr).contains("the model has no layers"));
+    assert_eq!(info.status.code(), Some(2));
+    assert_eq!(predict.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&predict.stderr).contains("the model has no layers"));
+}
+
+#[test]
+fn json_models_whose_shapes_do_not_match_are_rejected() {
+    let dir = work_dir("bad-shapes");
+    let model = dir.join("model.json");
+    let layer = r#"{"size": 1, "weights": [[0.5, -0.25], 1, 2], "bias": [[0.1, 0.2], 2, null]}"#;
+    std::fs::write(
+        &model,
+        format!(r#"{{"layers": [{}], "activation_function": null}}"#, layer),
+    )
+    .unwrap();
+    let model = model.to_str().unwrap();
+
+    let validate = only_brain(&["validate", model], "");
+    let predict = only_brain(&["predict", model], "1.0,2.0\n");
+
+    let message = "layer 1 has 1 neurons but 1 rows of weights and 2 biases";
+    assert_eq!(validate.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&validate.stderr).contains(message));
+    assert_eq!(predict.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&predict.stderr).contains(message));
+}
+
+#[test]
+fn diff_honors_the_format_flag() {
+    let dir = work_dir("diff-format");
+    let (_, model) = dumped_network(&dir);
+    let json = dir.join("model.json");
+    let renamed = dir.join("model.weights");
+    assert!(only_brain(&["convert", &model, json.to_str().unwrap()], "")
+        .status
+        .success());
+    std::fs::rename(&json, &renamed).unwrap();
+    let renamed = renamed.to_str().unwrap();
+
+    let guessed = only_brain(&["diff", renamed, renamed], "");
+    let explicit = only_brain(
+        &[
+            "diff",
+            renamed,
+            renamed,
+            "--format",
+            "json",
+            "--epsilon",
+            "0",
+        ],
+        "",
+    );
+
+    assert_eq!(guessed.status.code(), Some(2));
+    assert!(
+        explicit.status.success(),
+        "{}",
+        String::from_utf8_lossy(&explicit.stderr)
+    );
+}
@@ -0,0 +1,17 @@
+//! Helpers shared by the integration tests that build the crate again.
+
+use std::path::Path;
+use std::process::Command;
+
+/// Returns `cargo <args>` run from the crate root and building into `target_dir`. The
+/// `cargo test` running the tests holds the lock on the usual target directory, so a
+/// build there would wait for it forever.
+pub fn cargo(args: &[&str], target_dir: &Path) -> Command {
+    let mut command = Command::new(env!("CARGO"));
+    command
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(args)
+        .arg("--target-dir")
+        .arg(target_dir);
+    command
+}
@@ -0,0 +1,81 @@
+//! Builds the C ABI as a shared library and runs `tests/c/ffi_test.c` against it.
+#![cfg(unix)]
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+use nalgebra::{dmatrix, dvector};
+use only_brain::{dump_model, NeuralNetwork};
+
+mod common;
+
+fn run(command: &mut Command) -> Output {
+    let output = command.output().expect("command should start");
+    assert!(
+        output.status.success(),
+        "{:?} failed:\n{}{}",
+        command,
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output
+}
+
+fn sample_network() -> NeuralNetwork {
+    let mut nn = NeuralNetwork::new(&vec![2, 3, 2]);
+    nn.set_layer_weights(1, dmatrix![0.1, 0.2; 0.3, 0.4; 0.5, 0.6]);
+    nn.set_layer_biases(1, dvector![0.1, 0.2, 0.3]);
+    nn.set_layer_weights(2, dmatrix![0.9, 0.8, 0.7; 0.6, 0.5, 0.4]);
+    nn.set_layer_biases(2, dvector![0.1, 0.2]);
+    nn
+}
+
+#[test]
+fn a_c_program_can_load_and_run_a_model() {
+    if Command::new("cc").arg("--version").output().is_err() {
+        eprintln!("skipping: no C compiler found");
+        return;
+    }
+
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let work_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("ffi");
+    let target_dir = work_dir.join("target");
+    let lib_dir = target_dir.join("debug");
+    let executable = work_dir.join("ffi_test");
+    let model_path = work_dir.join("model.bin");
+    std::fs::create_dir_all(&work_dir).unwrap();
+
+    run(&mut common::cargo(
+        &[
+            "rustc",
+            "--lib",
+            "--features",
+            "ffi",
+            "--crate-type",
+            "cdylib",
+        ],
+        &target_dir,
+    ));
+
+    run(Command::new("cc")
+        .arg(manifest_dir.join("tests/c/ffi_test.c"))
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg("-L")
+        .arg(&lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .args(["-lonly_brain", "-o"])
+        .arg(&executable));
+
+    let nn = sample_network();
+    dump_model(&nn, model_path.to_str().unwrap()).unwrap();
+
+    let output = run(Command::new(&executable).arg(&model_path));
+    let printed: Vec<f64> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| line.parse().unwrap())
+        .collect();
+
+    assert_eq!(printed, nn.feed_forward(&vec![0.5, 0.2]));
+}
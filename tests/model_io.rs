@@ -5,7 +5,13 @@ use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU32, Ordering};
 
 use nalgebra::{dmatrix, dvector};
-use only_brain::{dump_model, load_model, ActivationFunction, NeuralNetwork};
+use only_brain::{
+    bvector, dump_model, dump_model_to_bytes, dump_perceptron, dump_pipeline, dump_quantized_model,
+    dump_sparse_model, load_model, load_model_from_bytes, load_perceptron, load_pipeline,
+    load_quantized_model, load_sparse_model, ActivationFunction, Dataset, MinMaxScaler,
+    ModelMetadata, NeuralNetwork, OutputTransform, Perceptron, Pipeline, PruneCriterion,
+    PruneScope, QuantizationGranularity, RobustScaler, StandardScaler,
+};
 
 const EPSILON: f64 = 1e-12;
 
@@ -62,7 +68,10 @@ fn sample_network() -> NeuralNetwork {
 fn assert_all_close(actual: &[f64], expected: &[f64]) {
     assert_eq!(actual.len(), expected.len(), "length mismatch");
     for (i, (a, e)) in actual.iter().zip(expected).enumerate() {
-        assert!((a - e).abs() < EPSILON, "at index {i}: expected {e}, got {a}");
+        assert!(
+            (a - e).abs() < EPSILON,
+            "at index {i}: expected {e}, got {a}"
+        );
     }
 }
 
@@ -121,6 +130,174 @@ fn the_activation_function_survives_a_round_trip() {
     );
 }
 
+#[test]
+fn a_fitted_input_scaler_is_saved_with_the_model() {
+    let temp = TempModelPath::new("scaler");
+    let train = Dataset::new(
+        vec![
+            vec![100.0, -3.0],
+            vec![250.0, 0.0],
+            vec![400.0, 9.0],
+            vec![1e6, 1.0],
+        ],
+        vec![vec![0.0]; 4],
+    );
+    let mut original = sample_network();
+    original.set_input_transform(RobustScaler::fit(&train));
+
+    dump_model(&original, temp.path().to_str().unwrap()).expect("dump should succeed");
+    let loaded = load_model(temp.path().to_str().unwrap()).expect("load should succeed");
+
+    assert_eq!(loaded, original);
+    assert_eq!(loaded.input_transform(), original.input_transform());
+    let raw = vec![300.0, 2.0];
+    assert_all_close(&loaded.feed_forward(&raw), &original.feed_forward(&raw));
+    assert_all_close(
+        &loaded.to_sparse().feed_forward(&raw),
+        &original.feed_forward(&raw),
+    );
+}
+
+#[test]
+fn metadata_is_saved_with_the_model() {
+    let temp = TempModelPath::new("metadata");
+    let mut original = sample_network();
+    let mut metadata = ModelMetadata {
+        description: "Scores two sensor readings".into(),
+        feature_names: vec!["temperature".into(), "pressure".into()],
+        output_labels: vec!["ok".into(), "faulty".into()],
+        ..ModelMetadata::new("sensor-check")
+    };
+    metadata.tags.insert("dataset".into(), "2024-q3".into());
+    original.set_metadata(metadata);
+
+    dump_model(&original, temp.path().to_str().unwrap()).expect("dump should succeed");
+    let loaded = load_model(temp.path().to_str().unwrap()).expect("load should succeed");
+
+    // The metadata only exists in version 2 files, which start with the format header.
+    assert_eq!(&fs::read(temp.path()).unwrap()[..8], b"OBNN\x02\0\0\0");
+    assert_eq!(loaded.metadata(), original.metadata());
+    assert!(loaded.to_string().contains("Outputs: ok, faulty"));
+}
+
+#[test]
+fn a_pipeline_is_saved_as_one_file() {
+    let temp = TempModelPath::new("pipeline");
+    let original = Pipeline::new(sample_network())
+        .with_input_transform(StandardScaler::fit_rows(&[
+            vec![10.0, 1.0],
+            vec![30.0, 5.0],
+        ]))
+        .with_output_transform(OutputTransform::InverseScale(
+            MinMaxScaler::fit_rows(&[vec![0.0, 1000.0], vec![50.0, 2000.0]]).into(),
+        ))
+        .with_output_transform(OutputTransform::Clip {
+            min: 0.0,
+            max: 1500.0,
+        });
+
+    dump_pipeline(&original, temp.path().to_str().unwrap()).expect("dump should succeed");
+    let loaded = load_pipeline(temp.path().to_str().unwrap()).expect("load should succeed");
+
+    assert_eq!(&fs::read(temp.path()).unwrap()[..8], b"OBPL\x01\0\0\0");
+    assert_eq!(loaded, original);
+    assert_eq!(loaded.predict(&[20.0, 2.0]), original.predict(&[20.0, 2.0]));
+}
+
+#[test]
+fn a_model_file_does_not_load_as_a_pipeline() {
+    let temp = TempModelPath::new("pipeline-mixup");
+    dump_model(&sample_network(), temp.path().to_str().unwrap()).unwrap();
+
+    let error = load_pipeline(temp.path().to_str().unwrap()).unwrap_err();
+
+    assert_eq!(error.to_string(), "the file is not a saved pipeline");
+}
+
+#[test]
+fn a_perceptron_round_trips_and_keeps_its_input_size() {
+    let temp = TempModelPath::new("perceptron");
+    let mut original = Perceptron::<f64, 3>::new(ActivationFunction::Tanh);
+    original.set_weights(bvector![0.5, -0.25, 2.0]);
+    original.set_bias(0.1);
+
+    dump_perceptron(&original, temp.path().to_str().unwrap()).expect("dump should succeed");
+    let loaded =
+        load_perceptron::<f64, 3>(temp.path().to_str().unwrap()).expect("load should succeed");
+    let wrong_size = load_perceptron::<f64, 2>(temp.path().to_str().unwrap());
+
+    assert_eq!(loaded, original);
+    let error = wrong_size.expect_err("a 3-input perceptron must not load as a 2-input one");
+    assert!(
+        error
+            .to_string()
+            .contains("expected a vector of 2 elements, got 3"),
+        "{error}"
+    );
+}
+
+#[test]
+fn a_single_precision_perceptron_round_trips() {
+    let temp = TempModelPath::new("perceptron-f32");
+    let mut original = Perceptron::<f32, 2>::new(ActivationFunction::Sigmoid);
+    original.set_weights(bvector![0.5, -0.25]);
+    original.set_bias(0.1);
+
+    dump_perceptron(&original, temp.path().to_str().unwrap()).expect("dump should succeed");
+    let loaded =
+        load_perceptron::<f32, 2>(temp.path().to_str().unwrap()).expect("load should succeed");
+
+    assert_eq!(loaded, original);
+}
+
+/// `tests/fixtures/model_v1.bin` is the `model.bin` written by the `dump_load` example
+/// before model files had a format header.
+#[test]
+fn a_model_saved_before_the_format_header_still_loads() {
+    let loaded = load_model(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/model_v1.bin"
+    ))
+    .expect("a version 1 model should load");
+
+    assert_eq!(loaded.num_layers(), 3);
+    assert_eq!(
+        loaded.layer_weights(1),
+        &dmatrix![0.99, 0.2; 0.3, 0.4; 0.5, 0.6]
+    );
+    assert_eq!(loaded.layer_biases(2), &dvector![0.1, 0.2]);
+    assert_eq!(loaded.activation_function(), ActivationFunction::BinaryStep);
+    assert!(loaded.input_transform().is_none());
+    assert!(loaded.metadata().is_none());
+
+    let resaved = load_model_from_bytes(&dump_model_to_bytes(&loaded).unwrap()).unwrap();
+    assert_eq!(resaved, loaded);
+}
+
+#[test]
+fn unknown_format_versions_and_trailing_bytes_are_rejected() {
+    let mut future = dump_model_to_bytes(&NeuralNetwork::new(&vec![2, 1])).unwrap();
+    future[4] = 99;
+    let mut padded = fs::read(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/model_v1.bin"
+    ))
+    .unwrap();
+    padded.push(0);
+    let mut padded_v2 = dump_model_to_bytes(&NeuralNetwork::new(&vec![2, 1])).unwrap();
+    padded_v2.push(0);
+
+    let error = load_model_from_bytes(&future).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("unsupported model format version 99"),
+        "{error}"
+    );
+    assert!(load_model_from_bytes(&padded).is_err());
+    assert!(load_model_from_bytes(&padded_v2).is_err());
+}
+
 #[test]
 fn loading_a_missing_file_returns_an_error_instead_of_panicking() {
     let missing = std::env::temp_dir().join("only-brain-does-not-exist.bin");
@@ -136,3 +313,45 @@ fn loading_a_file_that_is_not_a_model_returns_an_error() {
 
     assert!(load_model(temp.path().to_str().unwrap()).is_err());
 }
+
+#[test]
+fn a_quantized_model_round_trips_through_its_own_dump_and_load() {
+    let temp = TempModelPath::new("quantized");
+    let original = sample_network().quantize(QuantizationGranularity::PerRow);
+
+    dump_quantized_model(&original, temp.path().to_str().unwrap()).expect("dump should succeed");
+    let loaded = load_quantized_model(temp.path().to_str().unwrap()).expect("load should succeed");
+
+    assert_eq!(loaded, original);
+    assert_eq!(
+        loaded.feed_forward(&[0.5, 0.2]),
+        original.feed_forward(&[0.5, 0.2])
+    );
+}
+
+#[test]
+fn a_pruned_sparse_model_round_trips_and_is_smaller_on_disk() {
+    let dense_temp = TempModelPath::new("dense");
+    let sparse_temp = TempModelPath::new("sparse");
+    let mut original = NeuralNetwork::new(&vec![20, 20, 5]);
+    original.prune(PruneCriterion::Fraction(0.9), PruneScope::Global);
+    let sparse = original.to_sparse();
+
+    dump_model(&original, dense_temp.path().to_str().unwrap()).expect("dump should succeed");
+    dump_sparse_model(&sparse, sparse_temp.path().to_str().unwrap()).expect("dump should succeed");
+    let loaded =
+        load_sparse_model(sparse_temp.path().to_str().unwrap()).expect("load should succeed");
+
+    assert_eq!(loaded, sparse);
+    assert_eq!(loaded.to_dense(), original);
+    let report = original.sparsity_report();
+    assert_eq!(
+        fs::metadata(dense_temp.path()).unwrap().len(),
+        report.dense_bytes
+    );
+    assert_eq!(
+        fs::metadata(sparse_temp.path()).unwrap().len(),
+        report.sparse_bytes
+    );
+    assert!(report.sparse_bytes < report.dense_bytes);
+}
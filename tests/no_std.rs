@@ -0,0 +1,50 @@
+//! Checks that the inference core builds for a Cortex-M target without `std`.
+
+use std::path::Path;
+use std::process::Command;
+
+mod common;
+
+const TARGET: &str = "thumbv7em-none-eabihf";
+
+fn target_installed() -> bool {
+    let output = Command::new("rustc")
+        .args(["--print", "target-libdir", "--target", TARGET])
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => {
+            let libdir = String::from_utf8_lossy(&output.stdout);
+            Path::new(libdir.trim()).is_dir()
+        }
+        _ => false,
+    }
+}
+
+#[test]
+fn the_library_builds_for_thumbv7em_without_std() {
+    if !target_installed() {
+        eprintln!("skipping: install it with `rustup target add {TARGET}`");
+        return;
+    }
+
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("no_std");
+    let output = common::cargo(
+        &[
+            "build",
+            "--lib",
+            "--no-default-features",
+            "--target",
+            TARGET,
+        ],
+        &target_dir,
+    )
+    .output()
+    .expect("cargo should run");
+
+    assert!(
+        output.status.success(),
+        "no_std build failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}